@@ -0,0 +1,125 @@
+use std::{fs::File, io::BufWriter, path::Path, process::Command, time::Duration};
+
+use anyhow::Context;
+use common::{
+    bench::{BenchConfig, Samples},
+    input::InputSource,
+    AnyResult,
+};
+use serde::Serialize;
+
+use crate::{find_day, selection::DaySelection};
+
+/// Machine-readable benchmark results, written with `--json` so runs can be
+/// compared across commits.
+#[derive(Debug, Serialize)]
+struct Report {
+    /// `git describe` of the working tree, if available.
+    commit: Option<String>,
+    iterations: usize,
+    max_time_secs: f64,
+    results: Vec<StageResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct StageResult {
+    day: u8,
+    stage: &'static str,
+    samples: usize,
+    min_ns: u128,
+    median_ns: u128,
+}
+
+fn git_describe() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+fn write_report(path: &Path, report: &Report) -> AnyResult<()> {
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), report)?;
+    Ok(())
+}
+
+fn print_stage(day: u8, stage: &str, samples: &Samples) {
+    println!(
+        "day{day:<2} {stage:<5}  runs {:>3}  min {:>12.3?}  median {:>12.3?}",
+        samples.len(),
+        samples.min(),
+        samples.median()
+    );
+}
+
+/// Time parse, part 1 and part 2 of the selected days against their default
+/// inputs, printing min/median for each and optionally writing a JSON report.
+pub fn bench(
+    days: &DaySelection,
+    part: Option<u8>,
+    iterations: usize,
+    max_time: Duration,
+    json: Option<&Path>,
+) -> bool {
+    let config = BenchConfig {
+        iterations,
+        max_time,
+        part1: part.unwrap_or(1) == 1,
+        part2: part.unwrap_or(2) == 2,
+    };
+
+    let mut all_ok = true;
+    let mut results = vec![];
+    for &day_num in days.days() {
+        let day = find_day(day_num);
+        let timings = InputSource::resolve(day, None)
+            .read()
+            .and_then(|input| day.bench(&input, &config));
+        let timings = match timings {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("day{day_num}: {e:#}");
+                all_ok = false;
+                continue;
+            }
+        };
+
+        let stages = [
+            ("parse", Some(&timings.parse)),
+            ("part1", timings.part1.as_ref()),
+            ("part2", timings.part2.as_ref()),
+        ];
+        for (stage, samples) in stages {
+            let Some(samples) = samples else {
+                continue;
+            };
+            print_stage(day_num, stage, samples);
+            results.push(StageResult {
+                day: day_num,
+                stage,
+                samples: samples.len(),
+                min_ns: samples.min().as_nanos(),
+                median_ns: samples.median().as_nanos(),
+            });
+        }
+    }
+
+    if let Some(path) = json {
+        let report = Report {
+            commit: git_describe(),
+            iterations,
+            max_time_secs: max_time.as_secs_f64(),
+            results,
+        };
+        if let Err(e) = write_report(path, &report) {
+            eprintln!("{e:#}");
+            all_ok = false;
+        }
+    }
+    all_ok
+}
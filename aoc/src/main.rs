@@ -0,0 +1,182 @@
+mod bench;
+mod selection;
+mod verify;
+
+use std::{ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use clap::{Parser, Subcommand};
+use common::{input::InputSource, AnyResult, Day};
+use selection::DaySelection;
+
+const DAYS: [Day; 25] = [
+    day1::DAY,
+    day2::DAY,
+    day3::DAY,
+    day4::DAY,
+    day5::DAY,
+    day6::DAY,
+    day7::DAY,
+    day8::DAY,
+    day9::DAY,
+    day10::DAY,
+    day11::DAY,
+    day12::DAY,
+    day13::DAY,
+    day14::DAY,
+    day15::DAY,
+    day16::DAY,
+    day17::DAY,
+    day18::DAY,
+    day19::DAY,
+    day20::DAY,
+    day21::DAY,
+    day22::DAY,
+    day23::DAY,
+    day24::DAY,
+    day25::DAY,
+];
+
+/// Run Advent of Code 2022 solutions from a single entry point.
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the selected days against their inputs and print the answers
+    Run {
+        /// Days to run: `all`, `16`, `10..=15`, `10..15` or a comma-separated list
+        days: DaySelection,
+        /// Only run this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the input from FILE (or stdin for `-`) instead of the day's
+        /// default; only valid when a single day is selected
+        #[arg(long, value_name = "FILE")]
+        input: Option<String>,
+    },
+    /// Run the selected days and check the answers against each day's
+    /// `answers.toml`, failing if any differ
+    Verify {
+        /// Days to verify: `all`, `16`, `10..=15`, `10..15` or a comma-separated list
+        days: DaySelection,
+        /// Only verify this part (1 or 2)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Time parsing and each part of the selected days, reporting the
+    /// minimum and median of repeated runs
+    Bench {
+        /// Days to benchmark: `all`, `16`, `10..=15`, `10..15` or a comma-separated list
+        days: DaySelection,
+        /// Only benchmark this part (1 or 2); parsing is always timed
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Maximum number of runs of each stage
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+        iterations: u16,
+        /// Stop repeating a stage after this many seconds; it always runs once
+        #[arg(long, value_name = "SECS", default_value = "5", value_parser = parse_secs)]
+        max_time: Duration,
+        /// Also write the results to FILE as JSON
+        #[arg(long, value_name = "FILE")]
+        json: Option<PathBuf>,
+    },
+}
+
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("'{s}' is not a valid number of seconds"))
+}
+
+fn find_day(day: u8) -> &'static Day {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .expect("all days are registered")
+}
+
+/// Format an answer to follow a `label:`, starting multi-line answers (such
+/// as day 10's screen) on their own line.
+fn format_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{answer}")
+    } else {
+        format!(" {answer}")
+    }
+}
+
+fn parts(part: Option<u8>) -> RangeInclusive<u8> {
+    match part {
+        Some(p) => p..=p,
+        None => 1..=2,
+    }
+}
+
+fn run_part(day: &Day, part: u8, input: &str) -> AnyResult<()> {
+    let Some(part_fn) = day.part(part) else {
+        return Ok(());
+    };
+
+    let answer = part_fn(input)?;
+    println!("day{} / part{part}:{}", day.day, format_answer(&answer));
+    Ok(())
+}
+
+fn run(days: &DaySelection, part: Option<u8>, input_arg: Option<&str>) -> bool {
+    if input_arg.is_some() && days.days().len() > 1 {
+        eprintln!("--input can only be used when running a single day");
+        return false;
+    }
+
+    let mut all_ok = true;
+    for &day_num in days.days() {
+        let day = find_day(day_num);
+        let input = match InputSource::resolve(day, input_arg).read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day{day_num}: {e:#}");
+                all_ok = false;
+                continue;
+            }
+        };
+
+        for p in parts(part) {
+            if let Err(e) = run_part(day, p, &input) {
+                eprintln!("day{day_num} / part{p}: failed: {e:#}");
+                all_ok = false;
+            }
+        }
+    }
+    all_ok
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let success = match &cli.command {
+        Command::Run { days, part, input } => run(days, *part, input.as_deref()),
+        Command::Verify { days, part } => verify::verify(days, *part),
+        Command::Bench {
+            days,
+            part,
+            iterations,
+            max_time,
+            json,
+        } => bench::bench(
+            days,
+            *part,
+            *iterations as usize,
+            *max_time,
+            json.as_deref(),
+        ),
+    };
+
+    if !success {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+/// Which days to run, parsed from the command line: `all`, a single day
+/// like `16`, a range like `10..=15` or `10..15`, or a comma-separated
+/// list of any of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<u8>);
+impl DaySelection {
+    pub fn days(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = vec![];
+        for item in s.split(',').map(str::trim) {
+            if item == "all" {
+                days.extend(FIRST_DAY..=LAST_DAY);
+            } else if let Some((start, end)) = item.split_once("..=") {
+                days.extend(parse_day(start)?..=parse_day(end)?);
+            } else if let Some((start, end)) = item.split_once("..") {
+                days.extend(parse_day(start)?..parse_day(end)?);
+            } else {
+                days.push(parse_day(item)?);
+            }
+        }
+
+        days.sort_unstable();
+        days.dedup();
+        if days.is_empty() {
+            bail!("no days selected by '{s}'");
+        }
+        Ok(DaySelection(days))
+    }
+}
+
+fn parse_day(s: &str) -> anyhow::Result<u8> {
+    let day: u8 = s
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid day: '{s}'"))?;
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        bail!("day {day} is out of range {FIRST_DAY}..={LAST_DAY}");
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Vec<u8> {
+        s.parse::<DaySelection>().unwrap().0
+    }
+
+    #[test]
+    fn parse_single_day() {
+        assert_eq!(parse("16"), [16]);
+    }
+
+    #[test]
+    fn parse_all() {
+        assert_eq!(parse("all"), (1..=25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(parse("10..=15"), [10, 11, 12, 13, 14, 15]);
+        assert_eq!(parse("10..15"), [10, 11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn parse_list() {
+        assert_eq!(parse("3,1..=2,3"), [1, 2, 3]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("5..5".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use common::{
+    answers::{answers_path, Answers, Verdict},
+    input::InputSource,
+    AnyResult, Day,
+};
+
+use crate::{find_day, format_answer, parts, selection::DaySelection};
+
+#[derive(Debug, Default)]
+struct Tally {
+    correct: usize,
+    wrong: usize,
+    unrecorded: usize,
+    failed: usize,
+}
+
+impl Tally {
+    fn success(&self) -> bool {
+        self.wrong == 0 && self.failed == 0
+    }
+}
+
+fn load_answers(day: &Day) -> AnyResult<Answers> {
+    let path = answers_path(day);
+    match Answers::load(&path)? {
+        Some(answers) => Ok(answers),
+        None => {
+            eprintln!("day{}: no answers recorded at {}", day.day, path.display());
+            Ok(Answers::default())
+        }
+    }
+}
+
+fn verify_part(day: &Day, part: u8, input: &str, answers: &Answers, tally: &mut Tally) {
+    let Some(part_fn) = day.part(part) else {
+        return;
+    };
+    let label = format!("day{} / part{part}", day.day);
+
+    let actual = match part_fn(input) {
+        Ok(actual) => actual,
+        Err(e) => {
+            eprintln!("{label}: failed: {e:#}");
+            tally.failed += 1;
+            return;
+        }
+    };
+
+    let verdict = answers.check(part, &actual);
+    match &verdict {
+        Verdict::Correct => tally.correct += 1,
+        Verdict::Unrecorded => tally.unrecorded += 1,
+        Verdict::Wrong { .. } => tally.wrong += 1,
+    }
+
+    if let Verdict::Wrong { expected } = &verdict {
+        println!("{label}: {verdict}");
+        println!("  expected:{}", format_answer(expected));
+        println!("  actual:{}", format_answer(&actual));
+    } else {
+        println!("{label}: {verdict}");
+    }
+}
+
+/// Run the selected days against their default inputs and compare with the
+/// recorded answers. Returns `false` if any answer is wrong or any part
+/// fails to run; parts without a recorded answer are reported but don't fail.
+pub fn verify(days: &DaySelection, part: Option<u8>) -> bool {
+    let mut tally = Tally::default();
+
+    for &day_num in days.days() {
+        let day = find_day(day_num);
+        let loaded = InputSource::resolve(day, None)
+            .read()
+            .and_then(|input| Ok((input, load_answers(day)?)));
+        let (input, answers) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("day{day_num}: {e:#}");
+                tally.failed += 1;
+                continue;
+            }
+        };
+
+        for p in parts(part) {
+            verify_part(day, p, &input, &answers, &mut tally);
+        }
+    }
+
+    println!(
+        "{} correct, {} wrong, {} unrecorded, {} failed",
+        tally.correct, tally.wrong, tally.unrecorded, tally.failed
+    );
+    if !tally.success() {
+        eprintln!("verification FAILED");
+    }
+    tally.success()
+}
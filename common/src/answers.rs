@@ -0,0 +1,161 @@
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{input::INPUT_DIR_VAR, AnyResult, Day};
+
+/// File name of a day's recorded answers, next to its `input.txt`.
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+/// The known-good answers for a day's input, e.g.
+///
+/// ```toml
+/// part1 = 1741
+/// part2 = 2316
+/// ```
+///
+/// Either part may be left out if it isn't known yet. Answers can be given
+/// as integers or strings; multi-line answers use a `'''` string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    #[serde(default, deserialize_with = "answer")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    pub part2: Option<String>,
+}
+
+fn answer<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Int(i64),
+        Str(String),
+    }
+
+    Ok(Some(match Raw::deserialize(deserializer)? {
+        Raw::Int(i) => i.to_string(),
+        Raw::Str(s) => s,
+    }))
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> AnyResult<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Load recorded answers, or `None` if the file doesn't exist.
+    pub fn load(path: &Path) -> AnyResult<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let answers = Self::parse(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        Ok(Some(answers))
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self, part: u8, actual: &str) -> Verdict {
+        match self.part(part) {
+            None => Verdict::Unrecorded,
+            Some(expected) if expected.trim_end() == actual.trim_end() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.trim_end().to_owned(),
+            },
+        }
+    }
+}
+
+/// Outcome of comparing a computed answer with the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unrecorded,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::Wrong { .. } => write!(f, "WRONG"),
+            Verdict::Unrecorded => write!(f, "unrecorded"),
+        }
+    }
+}
+
+/// Where the answers for a day's default input live: `answers.toml` in the
+/// crate directory, or `$AOC_INPUT_DIR/dayN.answers.toml` when inputs come
+/// from there.
+pub fn answers_path(day: &Day) -> PathBuf {
+    answers_path_with_env(day, std::env::var_os(INPUT_DIR_VAR))
+}
+
+fn answers_path_with_env(day: &Day, input_dir: Option<OsString>) -> PathBuf {
+    match input_dir {
+        Some(dir) if !dir.is_empty() => {
+            Path::new(&dir).join(format!("day{}.answers.toml", day.day))
+        }
+        _ => day.dir().join(ANSWERS_FILE_NAME),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::tests::stub_day;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("part1 = 1741\npart2 = \"EFUGLPAP\"\n").unwrap();
+        assert_eq!(answers.part(1), Some("1741"));
+        assert_eq!(answers.part(2), Some("EFUGLPAP"));
+
+        let answers = Answers::parse("part1 = '''\n#..\n.#.\n'''\n").unwrap();
+        assert_eq!(answers.part(1), Some("#..\n.#.\n"));
+        assert_eq!(answers.part(2), None);
+
+        assert!(Answers::parse("part3 = 1").is_err());
+    }
+
+    #[test]
+    fn check_answers() {
+        let answers = Answers::parse("part1 = '''\n#..\n.#.\n'''\npart2 = 42").unwrap();
+        assert_eq!(answers.check(1, "#..\n.#."), Verdict::Correct);
+        assert_eq!(answers.check(2, "42"), Verdict::Correct);
+        assert_eq!(
+            answers.check(2, "43"),
+            Verdict::Wrong {
+                expected: "42".to_owned()
+            }
+        );
+        assert_eq!(Answers::default().check(1, "42"), Verdict::Unrecorded);
+    }
+
+    #[test]
+    fn path_follows_input_dir() {
+        let day = stub_day(3, "/crates/day3");
+        assert_eq!(
+            answers_path_with_env(&day, None),
+            PathBuf::from("/crates/day3/answers.toml")
+        );
+        assert_eq!(
+            answers_path_with_env(&day, Some("/inputs".into())),
+            PathBuf::from("/inputs/day3.answers.toml")
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{AnyResult, Solution};
+
+/// How much effort to spend measuring each stage of a day.
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Maximum number of times to run each stage.
+    pub iterations: usize,
+    /// Stop repeating a stage once this much time has been spent on it, so
+    /// slow days still finish. Each stage always runs at least once.
+    pub max_time: Duration,
+    pub part1: bool,
+    pub part2: bool,
+}
+
+/// Wall-clock times of repeated runs of a single stage.
+#[derive(Debug, Clone)]
+pub struct Samples(Vec<Duration>);
+impl Samples {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.0.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut sorted = self.0.clone();
+        sorted.sort_unstable();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 1 => sorted[n / 2],
+            n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        }
+    }
+}
+
+/// Timings for the stages of one day; parts that weren't requested are
+/// `None`.
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub parse: Samples,
+    pub part1: Option<Samples>,
+    pub part2: Option<Samples>,
+}
+
+/// Signature of [`bench_solution`] once monomorphised for a day, so it can
+/// be stored in a [`Day`](crate::Day).
+pub type BenchFn = fn(&str, &BenchConfig) -> AnyResult<DayBench>;
+
+fn sample<T>(config: &BenchConfig, mut stage: impl FnMut() -> AnyResult<T>) -> AnyResult<Samples> {
+    let started = Instant::now();
+    let mut samples = vec![];
+    while samples.is_empty()
+        || (samples.len() < config.iterations && started.elapsed() < config.max_time)
+    {
+        let t = Instant::now();
+        let output = stage()?;
+        samples.push(t.elapsed());
+        black_box(output);
+    }
+    Ok(Samples(samples))
+}
+
+/// Time parsing and each requested part separately. The parts are timed
+/// against a single parsed input, so they don't include the parsing cost.
+pub fn bench_solution<S: Solution>(input: &str, config: &BenchConfig) -> AnyResult<DayBench> {
+    let parse = sample(config, || S::parse(black_box(input)))?;
+    let parsed = S::parse(input)?;
+    let part1 = if config.part1 {
+        Some(sample(config, || S::part1(black_box(&parsed)))?)
+    } else {
+        None
+    };
+    let part2 = if config.part2 {
+        Some(sample(config, || S::part2(black_box(&parsed)))?)
+    } else {
+        None
+    };
+    Ok(DayBench {
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Samples {
+        Samples(ms.iter().map(|&m| Duration::from_millis(m)).collect())
+    }
+
+    #[test]
+    fn samples_stats() {
+        let odd = millis(&[5, 1, 3]);
+        assert_eq!(odd.min(), Duration::from_millis(1));
+        assert_eq!(odd.median(), Duration::from_millis(3));
+
+        let even = millis(&[4, 1, 2, 8]);
+        assert_eq!(even.median(), Duration::from_millis(3));
+    }
+
+    #[test]
+    fn sample_respects_limits() {
+        let mut config = BenchConfig {
+            iterations: 5,
+            max_time: Duration::from_secs(60),
+            part1: true,
+            part2: true,
+        };
+        assert_eq!(sample(&config, || Ok(())).unwrap().len(), 5);
+
+        config.max_time = Duration::ZERO;
+        assert_eq!(sample(&config, || Ok(())).unwrap().len(), 1);
+    }
+}
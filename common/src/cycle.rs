@@ -0,0 +1,308 @@
+//! Finding where a sequence of states starts repeating, so that a far-off
+//! step can be worked out without simulating everything before it.
+//!
+//! [`floyd`] and [`brent`] need only a step function and constant memory,
+//! but step through the sequence more than once; [`Cycle::measure`] steps
+//! through it once more to take the measurements needed to extrapolate.
+//! [`History`] steps through it once, remembering every state along with a
+//! measurement of it, and can extrapolate the measurement to any later step.
+
+use std::{collections::HashMap, hash::Hash};
+
+use anyhow::{anyhow, ensure};
+use num_traits::PrimInt;
+
+use crate::AnyResult;
+
+/// Where a sequence repeats: the state at `start + length` is the same as
+/// the state at `start`, so everything from `start` on repeats every
+/// `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// A measurement at step `n`, from the measurements at steps
+    /// `0..=start + length`, assuming it changes by the same amount every
+    /// time round the cycle. It may fall as well as rise, even if `M` is
+    /// unsigned.
+    ///
+    /// Fails if there are too few measurements, or the result doesn't fit in
+    /// `M`.
+    pub fn extrapolate<M: PrimInt>(&self, measurements: &[M], n: usize) -> AnyResult<M> {
+        let end = self.start + self.length;
+        ensure!(
+            measurements.len() > end,
+            "measurements are needed up to step {end}, but there are only {}",
+            measurements.len()
+        );
+        if n <= end {
+            return Ok(measurements[n]);
+        }
+        let wide = |m: M| m.to_i128().ok_or_else(|| anyhow!("measurement too large"));
+        let gain = wide(measurements[end])? - wide(measurements[self.start])?;
+        let cycles = i128::try_from((n - self.start) / self.length)?;
+        let base = wide(measurements[self.equivalent_step(n)])?;
+        gain.checked_mul(cycles)
+            .and_then(|change| base.checked_add(change))
+            .and_then(|m| M::from(m))
+            .ok_or_else(|| anyhow!("the measurement at step {n} is out of range"))
+    }
+
+    /// The measurements at steps `0..=start + length` of the sequence from
+    /// `initial`, as [`extrapolate`](Self::extrapolate) needs, for a cycle
+    /// found by [`floyd`] or [`brent`], which don't keep them. `measure`
+    /// sees each state once, in order, so it can keep a running total.
+    pub fn measure<S, M>(
+        &self,
+        initial: S,
+        mut step: impl FnMut(&S) -> S,
+        mut measure: impl FnMut(&S) -> M,
+    ) -> Vec<M> {
+        let mut state = initial;
+        let mut measurements = vec![measure(&state)];
+        for _ in 0..self.start + self.length {
+            state = step(&state);
+            measurements.push(measure(&state));
+        }
+        measurements
+    }
+}
+
+/// Floyd's tortoise and hare: the cycle in the sequence starting at
+/// `initial`, where each state follows from the last by `step`. Never
+/// returns if the sequence doesn't repeat.
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // the hare goes twice as fast, so they meet somewhere in the cycle
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let hare_half = step(&hare);
+        hare = step(&hare_half);
+    }
+
+    // the hare is now a whole number of cycles ahead, so stepping both at
+    // the same speed, one from the start, they meet where the cycle starts
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm: the same as [`floyd`], but usually with fewer steps.
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by teleporting the tortoise to the hare at each power
+    // of two, until the hare comes back round to it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare one length ahead, they meet where the cycle starts
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Every state seen so far in a sequence, with a measurement of each.
+#[derive(Debug, Clone)]
+pub struct History<S, M> {
+    seen: HashMap<S, usize>,
+    measurements: Vec<M>,
+}
+
+impl<S: Hash + Eq, M: PrimInt> History<S, M> {
+    pub fn new() -> Self {
+        History {
+            seen: HashMap::new(),
+            measurements: vec![],
+        }
+    }
+
+    /// The number of states recorded.
+    pub fn len(&self) -> usize {
+        self.measurements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.measurements.is_empty()
+    }
+
+    /// Record the next state in the sequence, and its measurement. Returns
+    /// the cycle if the state has been seen before.
+    pub fn push(&mut self, state: S, measurement: M) -> Option<Cycle> {
+        let step = self.measurements.len();
+        self.measurements.push(measurement);
+        match self.seen.get(&state) {
+            Some(&start) => Some(Cycle {
+                start,
+                length: step - start,
+            }),
+            None => {
+                self.seen.insert(state, step);
+                None
+            }
+        }
+    }
+
+    /// The measurements recorded, in order.
+    pub fn measurements(&self) -> &[M] {
+        &self.measurements
+    }
+
+    /// The measurement at step `n`, given the cycle found by
+    /// [`push`](Self::push); see [`Cycle::extrapolate`].
+    pub fn extrapolate(&self, cycle: &Cycle, n: usize) -> AnyResult<M> {
+        cycle.extrapolate(&self.measurements, n)
+    }
+}
+
+impl<S: Hash + Eq, M: PrimInt> Default for History<S, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x -> x^2 + 1 mod 255 from 3 goes 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn all_modes_agree() {
+        let expected = Cycle {
+            start: 2,
+            length: 6,
+        };
+        assert_eq!(floyd(3, next), expected);
+        assert_eq!(brent(3, next), expected);
+
+        let mut history = History::new();
+        let mut x = 3;
+        let cycle = loop {
+            if let Some(cycle) = history.push(x, x) {
+                break cycle;
+            }
+            x = next(&x);
+        };
+        assert_eq!(cycle, expected);
+        assert_eq!(history.len(), 9);
+    }
+
+    #[test]
+    fn cycle_from_the_start() {
+        let expected = Cycle {
+            start: 0,
+            length: 5,
+        };
+        assert_eq!(floyd(0, |x| (x + 1) % 5), expected);
+        assert_eq!(brent(0, |x| (x + 1) % 5), expected);
+    }
+
+    #[test]
+    fn extrapolates_measurements() {
+        // a sawtooth climbing 3 every 4 steps, after a run-in of 2
+        let cycle = Cycle {
+            start: 2,
+            length: 4,
+        };
+        let heights = [0_u64, 1, 5, 7, 6, 7, 8];
+        let by_hand = |n: usize| match n {
+            0 | 1 => heights[n],
+            _ => heights[2 + (n - 2) % 4] + 3 * ((n - 2) / 4) as u64,
+        };
+        for n in 0..50 {
+            assert_eq!(cycle.extrapolate(&heights, n).unwrap(), by_hand(n));
+        }
+        assert_eq!(cycle.equivalent_step(1), 1);
+        assert_eq!(cycle.equivalent_step(11), 3);
+        assert_eq!(
+            cycle.extrapolate(&heights, 1_000_000_000_002).unwrap(),
+            5 + 750_000_000_000
+        );
+        assert!(cycle.extrapolate(&heights[..6], 10).is_err());
+    }
+
+    #[test]
+    fn extrapolates_falling_measurements() {
+        // falling 10 every 2 steps, after a run-in of 1
+        let cycle = Cycle {
+            start: 1,
+            length: 2,
+        };
+        let levels = [100_u64, 90, 85, 80];
+        assert_eq!(cycle.extrapolate(&levels, 5).unwrap(), 70);
+        assert_eq!(cycle.extrapolate(&levels, 6).unwrap(), 65);
+        assert_eq!(cycle.extrapolate(&levels, 19).unwrap(), 0);
+        // below zero
+        assert!(cycle.extrapolate(&levels, 20).is_err());
+    }
+
+    #[test]
+    fn extrapolates_after_brent() {
+        // a running total of the states rises by the same amount each time
+        // round the cycle
+        let running_total = || {
+            let mut total = 0;
+            move |&x: &u32| {
+                total += u64::from(x);
+                total
+            }
+        };
+        let cycle = brent(3, next);
+        let totals = cycle.measure(3, next, running_total());
+        assert_eq!(totals.len(), 9);
+
+        let mut x = 3;
+        let mut total = running_total();
+        for n in 0..100 {
+            assert_eq!(cycle.extrapolate(&totals, n).unwrap(), total(&x));
+            x = next(&x);
+        }
+    }
+}
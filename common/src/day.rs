@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use crate::{
+    bench::{bench_solution, BenchConfig, BenchFn, DayBench},
+    input::read_input,
+    solution::{solve_part1, solve_part2},
+    AnyResult, Solution,
+};
+
+/// Signature shared by every day's entry points: take the raw puzzle input
+/// and produce the answer as a string.
+pub type PartFn = fn(&str) -> AnyResult<String>;
+
+/// Describes how to run a single day, so that tooling (e.g. the `aoc` runner)
+/// can drive any of them without knowing their internal types.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    /// The day's crate directory, which holds its default `input.txt`.
+    pub dir: &'static str,
+    pub part1: PartFn,
+    pub part2: Option<PartFn>,
+    pub bench: BenchFn,
+}
+impl Day {
+    /// Describe `day`, solved by `S`; `dir` is normally
+    /// `env!("CARGO_MANIFEST_DIR")` of the day's crate.
+    pub const fn new<S: Solution>(day: u8, dir: &'static str) -> Self {
+        Day {
+            day,
+            dir,
+            part1: solve_part1::<S>,
+            part2: Some(solve_part2::<S>),
+            bench: bench_solution::<S>,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        Path::new(self.dir)
+    }
+
+    /// Time the stages of this day; part 2 is skipped for days without one.
+    pub fn bench(&self, input: &str, config: &BenchConfig) -> AnyResult<DayBench> {
+        let config = BenchConfig {
+            part2: config.part2 && self.part2.is_some(),
+            ..config.clone()
+        };
+        (self.bench)(input, &config)
+    }
+
+    pub fn part(&self, part: u8) -> Option<PartFn> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+/// Run both parts of a day against its input, printing the results. The input
+/// is resolved from the command line and environment; see
+/// [`InputSource`](crate::input::InputSource).
+pub fn run_day(day: &Day) -> AnyResult<()> {
+    print_answers(day, &read_input(day)?)
+}
+
+/// Solve both parts of a day for `input`, printing the results.
+pub fn print_answers(day: &Day, input: &str) -> AnyResult<()> {
+    for part in 1..=2 {
+        if let Some(part_fn) = day.part(part) {
+            let answer = part_fn(input)?;
+            println!("day{} / part{part}: {answer}", day.day);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A day with no real solution, for tests that only care about its number
+    /// and directory.
+    pub const fn stub_day(day: u8, dir: &'static str) -> Day {
+        Day {
+            day,
+            dir,
+            part1: |_| Ok(String::new()),
+            part2: None,
+            bench: |_, _| anyhow::bail!("stub day"),
+        }
+    }
+}
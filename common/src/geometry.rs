@@ -0,0 +1,482 @@
+//! 2D points, directions and dense grids shared between the grid-based days.
+//!
+//! Coordinates follow the puzzle inputs: `x` increases to the right and `y`
+//! increases down the page, so [`Dir4::N`] is `(0, -1)`.
+
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+use anyhow::bail;
+use num_traits::{PrimInt, Signed};
+
+use crate::{parse::Source, AnyResult};
+
+/// Integer types usable as point coordinates.
+pub trait Coord: PrimInt + Signed + Hash + Debug + 'static {}
+impl<T: PrimInt + Signed + Hash + Debug + 'static> Coord for T {}
+
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+impl<T: Coord> Point<T> {
+    pub fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn manhattan_length(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T {
+        (self - other).manhattan_length()
+    }
+
+    /// The four orthogonally adjacent points.
+    pub fn neighbours4(self) -> [Self; 4] {
+        Dir4::ALL.map(|d| self + d.into())
+    }
+
+    /// The eight adjacent points, including diagonals.
+    pub fn neighbours8(self) -> [Self; 8] {
+        Dir8::ALL.map(|d| self + d.into())
+    }
+}
+
+impl<T: Coord> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+impl<T: Coord> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+impl<T: Coord> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+impl<T: Coord> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+impl<T: Coord> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<T: Coord> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// The four orthogonal directions.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+impl Dir4 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    pub fn clockwise(self) -> Dir4 {
+        match self {
+            Dir4::N => Dir4::E,
+            Dir4::E => Dir4::S,
+            Dir4::S => Dir4::W,
+            Dir4::W => Dir4::N,
+        }
+    }
+
+    pub fn counter_clockwise(self) -> Dir4 {
+        self.clockwise().opposite()
+    }
+
+    pub fn opposite(self) -> Dir4 {
+        self.clockwise().clockwise()
+    }
+}
+impl<T: Coord> From<Dir4> for Point<T> {
+    fn from(dir: Dir4) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        match dir {
+            Dir4::N => Point::new(zero, -one),
+            Dir4::E => Point::new(one, zero),
+            Dir4::S => Point::new(zero, one),
+            Dir4::W => Point::new(-one, zero),
+        }
+    }
+}
+
+/// The eight directions, including diagonals.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+impl Dir8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+}
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::N => Dir8::N,
+            Dir4::E => Dir8::E,
+            Dir4::S => Dir8::S,
+            Dir4::W => Dir8::W,
+        }
+    }
+}
+impl<T: Coord> From<Dir8> for Point<T> {
+    fn from(dir: Dir8) -> Self {
+        let orthogonal = |d: Dir4| Point::<T>::from(d);
+        match dir {
+            Dir8::N => orthogonal(Dir4::N),
+            Dir8::NE => orthogonal(Dir4::N) + orthogonal(Dir4::E),
+            Dir8::E => orthogonal(Dir4::E),
+            Dir8::SE => orthogonal(Dir4::S) + orthogonal(Dir4::E),
+            Dir8::S => orthogonal(Dir4::S),
+            Dir8::SW => orthogonal(Dir4::S) + orthogonal(Dir4::W),
+            Dir8::W => orthogonal(Dir4::W),
+            Dir8::NW => orthogonal(Dir4::N) + orthogonal(Dir4::W),
+        }
+    }
+}
+
+/// A dense, row-major grid addressed by [`Point`]s. Lookups outside the grid,
+/// including negative coordinates, return `None` rather than panicking.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    values: Vec<T>,
+    width: usize,
+    height: usize,
+}
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill_value: T) -> Self {
+        Grid {
+            values: vec![fill_value; width * height],
+            width,
+            height,
+        }
+    }
+}
+impl<T> Grid<T> {
+    /// Build a grid from row-major values.
+    pub fn from_vec(width: usize, height: usize, values: Vec<T>) -> AnyResult<Self> {
+        if values.len() != width * height {
+            bail!(
+                "expected {width}x{height} = {} values, got {}",
+                width * height,
+                values.len()
+            );
+        }
+        Ok(Grid {
+            values,
+            width,
+            height,
+        })
+    }
+
+    /// Parse a rectangular character map, one row per line, converting each
+    /// character with `parse_char`. Failures are reported as a
+    /// [`ParseError`] at the offending character or row.
+    pub fn parse_chars(
+        input: &str,
+        mut parse_char: impl FnMut(char) -> AnyResult<T>,
+    ) -> AnyResult<Self> {
+        let src = Source::new(input);
+        let mut values = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for line in input.lines() {
+            let before = values.len();
+            for (i, ch) in line.char_indices() {
+                let value = parse_char(ch)
+                    .map_err(|e| src.error(&line[i..i + ch.len_utf8()], format!("{e:#}")))?;
+                values.push(value);
+            }
+            let line_width = values.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(src
+                        .error(line, format!("row has width {line_width}, expected {w}"))
+                        .into());
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                values,
+                width,
+                height,
+            }),
+            _ => Err(src.end_error("empty grid").into()),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of<C: Coord>(&self, point: Point<C>) -> Option<usize> {
+        let x = point.x.to_usize()?;
+        let y = point.y.to_usize()?;
+        (x < self.width && y < self.height).then_some(x + y * self.width)
+    }
+
+    fn point_of<C: Coord>(&self, index: usize) -> Point<C> {
+        let coord = |v: usize| C::from(v).expect("grid dimension fits in coordinate type");
+        Point::new(coord(index % self.width), coord(index / self.width))
+    }
+
+    pub fn contains<C: Coord>(&self, point: Point<C>) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get<C: Coord>(&self, point: Point<C>) -> Option<&T> {
+        self.index_of(point).map(|ix| &self.values[ix])
+    }
+
+    pub fn get_mut<C: Coord>(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.index_of(point).map(|ix| &mut self.values[ix])
+    }
+
+    /// Every point in the grid, in row-major order.
+    pub fn points<C: Coord>(&self) -> impl Iterator<Item = Point<C>> + '_ {
+        (0..self.values.len()).map(|ix| self.point_of(ix))
+    }
+
+    /// Every point in the grid with its value, in row-major order.
+    pub fn iter<C: Coord>(&self) -> impl Iterator<Item = (Point<C>, &T)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(ix, v)| (self.point_of(ix), v))
+    }
+
+    /// The first point, in row-major order, whose value matches `predicate`.
+    pub fn position<C: Coord>(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point<C>> {
+        self.values
+            .iter()
+            .position(predicate)
+            .map(|ix| self.point_of(ix))
+    }
+
+    /// The orthogonal neighbours of `point` that are inside the grid.
+    pub fn neighbours4<C: Coord>(
+        &self,
+        point: Point<C>,
+    ) -> impl Iterator<Item = (Point<C>, &T)> + '_ {
+        point
+            .neighbours4()
+            .into_iter()
+            .filter_map(|p| self.get(p).map(|v| (p, v)))
+    }
+
+    /// The neighbours of `point`, including diagonals, that are inside the
+    /// grid.
+    pub fn neighbours8<C: Coord>(
+        &self,
+        point: Point<C>,
+    ) -> impl Iterator<Item = (Point<C>, &T)> + '_ {
+        point
+            .neighbours8()
+            .into_iter()
+            .filter_map(|p| self.get(p).map(|v| (p, v)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.values[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, even when the grid has no columns, so that each is empty.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} out of range");
+        self.values.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            values: self.values.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T, C: Coord> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<C>) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+impl<T, C: Coord> IndexMut<Point<C>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<C>) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for element in row {
+                write!(f, "{element}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::ParseError, OptionAnyhow};
+
+    type P = Point<i32>;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse_chars(input, |ch| Ok(ch.to_digit(10).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        let p = P::new(3, -4);
+        assert_eq!(p + P::new(1, 1), P::new(4, -3));
+        assert_eq!(p - P::new(1, 1), P::new(2, -5));
+        assert_eq!(p * 2, P::new(6, -8));
+        assert_eq!(p.signum(), P::new(1, -1));
+        assert_eq!(p.manhattan_length(), 7);
+        assert_eq!(p.manhattan_distance(P::zero()), 7);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(P::from(Dir4::N), P::new(0, -1));
+        assert_eq!(P::from(Dir8::SE), P::new(1, 1));
+        assert_eq!(Dir4::N.clockwise(), Dir4::E);
+        assert_eq!(Dir4::N.counter_clockwise(), Dir4::W);
+        assert_eq!(Dir4::E.opposite(), Dir4::W);
+        assert_eq!(P::zero().neighbours8().len(), 8);
+    }
+
+    #[test]
+    fn grid_access() {
+        let mut grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(P::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(P::new(3, 1)), None);
+        assert_eq!(grid.get(P::new(-1, 0)), None);
+
+        *grid.get_mut(P::new(0, 0)).unwrap() = 9;
+        grid[P::new(1, 0)] = 8;
+        assert_eq!(grid.to_string(), "983\n456\n");
+        assert_eq!(grid.position(|&v| v == 4), Some(P::new(0, 1)));
+    }
+
+    #[test]
+    fn grid_iterators() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+
+        let mut neighbours: Vec<u32> = grid.neighbours4(P::new(0, 0)).map(|(_, v)| *v).collect();
+        neighbours.sort();
+        assert_eq!(neighbours, [2, 4]);
+        assert_eq!(grid.neighbours8(P::new(1, 0)).count(), 5);
+        assert_eq!(grid.iter::<i32>().nth(4), Some((P::new(1, 1), &5)));
+    }
+
+    #[test]
+    fn grid_parse_errors() {
+        assert!(Grid::parse_chars("", Ok).is_err());
+        assert!(Grid::parse_chars("ab\nc\n", Ok).is_err());
+
+        let err = Grid::parse_chars("12\n3x\n", |ch| ch.to_digit(10).ok_anyhow()).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        assert!(Grid::from_vec(2, 2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn empty_grids() {
+        let grid = Grid::<u8>::from_vec(0, 3, vec![]).unwrap();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[], [], []]);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.points::<i32>().count(), 0);
+        assert_eq!(grid.get(P::zero()), None);
+        assert_eq!(grid.to_string(), "\n\n\n");
+
+        let grid = Grid::new(4, 0, 'x');
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(
+            grid.columns().map(|c| c.count()).collect::<Vec<_>>(),
+            [0; 4]
+        );
+    }
+}
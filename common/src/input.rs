@@ -0,0 +1,277 @@
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context};
+
+use crate::{AnyResult, Day};
+
+/// Environment variable naming a directory of inputs, laid out as `day1.txt`,
+/// `day2.txt`, and so on.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// File name of a day's input within its crate directory.
+pub const INPUT_FILE_NAME: &str = "input.txt";
+
+/// Where a day's puzzle input is read from.
+///
+/// Resolution is the same for every day, in order of precedence:
+/// 1. an explicit `--input FILE` argument, where `-` means stdin;
+/// 2. `$AOC_INPUT_DIR/dayN.txt`, if the variable is set;
+/// 3. `input.txt` in the day's crate directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Resolve the input for `day`, given the value of an `--input` argument
+    /// if there was one.
+    pub fn resolve(day: &Day, input_arg: Option<&str>) -> Self {
+        Self::resolve_with_env(day, input_arg, std::env::var_os(INPUT_DIR_VAR))
+    }
+
+    fn resolve_with_env(day: &Day, input_arg: Option<&str>, input_dir: Option<OsString>) -> Self {
+        match input_arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.into()),
+            None => match input_dir {
+                Some(dir) if !dir.is_empty() => {
+                    InputSource::File(Path::new(&dir).join(format!("day{}.txt", day.day)))
+                }
+                _ => InputSource::File(day.dir().join(INPUT_FILE_NAME)),
+            },
+        }
+    }
+
+    pub fn read(&self) -> AnyResult<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut contents)
+                    .context("failed to read input from stdin")?;
+                Ok(contents)
+            }
+            InputSource::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// An option a standalone day binary understands besides `--input`, given
+/// as `--name`, or as `--name VALUE` (or `--name=VALUE`) if it takes a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayOption {
+    pub name: &'static str,
+    /// What the value stands for, shown in the usage message; `None` for an
+    /// option that's just a flag.
+    pub value: Option<&'static str>,
+}
+
+impl DayOption {
+    pub const fn flag(name: &'static str) -> Self {
+        DayOption { name, value: None }
+    }
+
+    pub const fn with_value(name: &'static str, value: &'static str) -> Self {
+        DayOption {
+            name,
+            value: Some(value),
+        }
+    }
+}
+
+impl Display for DayOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "[--{} {value}]", self.name),
+            None => write!(f, "[--{}]", self.name),
+        }
+    }
+}
+
+/// The arguments given to a standalone day binary: where to read the input
+/// from, and any of the day's own options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayArgs {
+    /// The value of `--input`, if given.
+    pub input: Option<String>,
+    options: Vec<(&'static str, Option<String>)>,
+}
+
+impl DayArgs {
+    /// Parse the process arguments, allowing `options` as well as `--input`.
+    pub fn from_env(options: &[DayOption]) -> AnyResult<Self> {
+        Self::parse(std::env::args().skip(1), options)
+    }
+
+    fn parse(args: impl IntoIterator<Item = String>, options: &[DayOption]) -> AnyResult<Self> {
+        let usage = || {
+            let options = options.iter().map(|o| format!(" {o}")).collect::<String>();
+            format!("usage: [--input FILE|-]{options}")
+        };
+        let mut parsed = DayArgs::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                bail!("unexpected argument '{arg}'; {}", usage());
+            };
+            let (name, inline) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (name, None),
+            };
+            if name == "input" {
+                match inline.or_else(|| args.next()) {
+                    Some(value) => parsed.input = Some(value),
+                    None => bail!("--input requires a file name, or - for stdin"),
+                }
+                continue;
+            }
+            let Some(option) = options.iter().find(|o| o.name == name) else {
+                bail!("unexpected argument '{arg}'; {}", usage());
+            };
+            let value = match (option.value, inline) {
+                (None, None) => None,
+                (None, Some(_)) => bail!("--{name} doesn't take a value"),
+                (Some(_), Some(value)) => Some(value),
+                (Some(value), None) => match args.next() {
+                    Some(given) => Some(given),
+                    None => bail!("--{name} requires a value: {value}"),
+                },
+            };
+            parsed.options.push((option.name, value));
+        }
+        Ok(parsed)
+    }
+
+    /// Whether the option `name` was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| *n == name)
+    }
+
+    /// The value given for the option `name`, the last one if it was given
+    /// more than once.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// The value given for the option `name`, parsed.
+    pub fn parse_value<T>(&self, name: &str) -> AnyResult<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .with_context(|| format!("invalid value '{value}' for --{name}"))
+            })
+            .transpose()
+    }
+
+    /// Read the input for `day`, from `--input` if it was given, or else as
+    /// described by [`InputSource`].
+    pub fn read_input(&self, day: &Day) -> AnyResult<String> {
+        InputSource::resolve(day, self.input.as_deref()).read()
+    }
+}
+
+/// Read the input for `day` as resolved from the process arguments and
+/// environment.
+pub fn read_input(day: &Day) -> AnyResult<String> {
+    DayArgs::from_env(&[])?.read_input(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::tests::stub_day;
+
+    const DAY: Day = stub_day(7, "/crates/day7");
+
+    fn args(args: &[&str]) -> AnyResult<Option<String>> {
+        Ok(DayArgs::parse(args.iter().map(|s| s.to_string()), &[])?.input)
+    }
+
+    #[test]
+    fn resolve_defaults_to_crate_dir() {
+        assert_eq!(
+            InputSource::resolve_with_env(&DAY, None, None),
+            InputSource::File("/crates/day7/input.txt".into())
+        );
+    }
+
+    #[test]
+    fn resolve_precedence() {
+        let env = || Some(OsString::from("/inputs"));
+        assert_eq!(
+            InputSource::resolve_with_env(&DAY, None, env()),
+            InputSource::File("/inputs/day7.txt".into())
+        );
+        assert_eq!(
+            InputSource::resolve_with_env(&DAY, Some("mine.txt"), env()),
+            InputSource::File("mine.txt".into())
+        );
+        assert_eq!(
+            InputSource::resolve_with_env(&DAY, Some("-"), env()),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(args(&[]).unwrap(), None);
+        assert_eq!(args(&["--input", "a.txt"]).unwrap(), Some("a.txt".into()));
+        assert_eq!(args(&["--input=-"]).unwrap(), Some("-".into()));
+        assert!(args(&["--input"]).is_err());
+        assert!(args(&["a.txt"]).is_err());
+    }
+
+    #[test]
+    fn parse_day_options() {
+        const OPTIONS: &[DayOption] =
+            &[DayOption::flag("plan"), DayOption::with_value("width", "N")];
+        let parse = |args: &[&str]| DayArgs::parse(args.iter().map(|s| s.to_string()), OPTIONS);
+
+        let parsed = parse(&["--width", "9", "--plan", "--input", "a.txt", "--width=10"]).unwrap();
+        assert_eq!(parsed.input.as_deref(), Some("a.txt"));
+        assert!(parsed.flag("plan"));
+        assert_eq!(parsed.parse_value::<usize>("width").unwrap(), Some(10));
+
+        let parsed = parse(&[]).unwrap();
+        assert!(!parsed.flag("plan"));
+        assert_eq!(parsed.parse_value::<usize>("width").unwrap(), None);
+
+        assert!(parse(&["--width"]).is_err());
+        assert!(parse(&["--plan=yes"]).is_err());
+        assert!(parse(&["--height", "3"]).is_err());
+        assert!(parse(&["--width", "x"])
+            .unwrap()
+            .parse_value::<usize>("width")
+            .is_err());
+        let err = parse(&["plan"]).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("usage: [--input FILE|-] [--plan] [--width N]"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Sets of integers kept as sorted, disjoint inclusive ranges, for puzzles
+//! about which stretches of a line are covered.
+
+use std::ops::RangeInclusive;
+
+use num_traits::PrimInt;
+
+/// A set of integers, stored as the fewest inclusive ranges that cover it.
+/// The ranges are kept sorted, and never overlap or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(lo, hi)| lo..=hi)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, hi)| hi < value);
+        self.ranges.get(i).is_some_and(|&(lo, _)| lo <= value)
+    }
+
+    /// The number of integers in the set, or `None` if that's too many to
+    /// count in a `T`, as for every value of the type.
+    pub fn total_len(&self) -> Option<T> {
+        self.ranges.iter().try_fold(T::zero(), |total, &(lo, hi)| {
+            total.checked_add(&hi.checked_sub(&lo)?.checked_add(&T::one())?)
+        })
+    }
+
+    /// Add every integer in `range`, joining up any ranges it overlaps or
+    /// touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        let start = self
+            .ranges
+            .partition_point(|&(_, h)| h < lo.saturating_sub(T::one()));
+        let end = self
+            .ranges
+            .partition_point(|&(l, _)| l <= hi.saturating_add(T::one()));
+        if start < end {
+            lo = lo.min(self.ranges[start].0);
+            hi = hi.max(self.ranges[end - 1].1);
+        }
+        self.ranges.splice(start..end, [(lo, hi)]);
+    }
+
+    /// Take out every integer in `range`, splitting any range it falls
+    /// inside.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (lo, hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+        let start = self.ranges.partition_point(|&(_, h)| h < lo);
+        let end = self.ranges.partition_point(|&(l, _)| l <= hi);
+        if start == end {
+            return;
+        }
+        let mut kept = vec![];
+        let (first_lo, _) = self.ranges[start];
+        let (_, last_hi) = self.ranges[end - 1];
+        if first_lo < lo {
+            kept.push((first_lo, lo - T::one()));
+        }
+        if last_hi > hi {
+            kept.push((hi + T::one(), last_hi));
+        }
+        self.ranges.splice(start..end, kept);
+    }
+
+    /// Everything in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    /// Everything in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_lo, a_hi)), Some(&(b_lo, b_hi))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (lo, hi) = (a_lo.max(b_lo), a_hi.min(b_hi));
+            if lo <= hi {
+                ranges.push((lo, hi));
+            }
+            // whichever ends first can't overlap anything further on
+            if a_hi < b_hi {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Everything in `bounds` that isn't in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        IntervalSet {
+            ranges: self.gaps(bounds).map(|r| r.into_inner()).collect(),
+        }
+    }
+
+    /// The stretches of `bounds` the set doesn't cover, in order.
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (lo, hi) = bounds.into_inner();
+        // the next value that might be in a gap, or none once past the end
+        let mut next = (lo <= hi).then_some(lo);
+        let mut ranges = self
+            .ranges
+            .iter()
+            .filter(move |&&(l, h)| h >= lo && l <= hi);
+        std::iter::from_fn(move || loop {
+            let from = next?;
+            match ranges.next() {
+                Some(&(l, h)) => {
+                    next = h.checked_add(&T::one()).filter(|&n| n <= hi);
+                    if l > from {
+                        return Some(from..=l - T::one());
+                    }
+                }
+                None => {
+                    next = None;
+                    return Some(from..=hi);
+                }
+            }
+        })
+    }
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert_joins_overlapping_and_touching() {
+        let mut set: IntervalSet<i32> = [1..=3, 10..=12, 6..=7].into_iter().collect();
+        assert_eq!(ranges(&set), [1..=3, 6..=7, 10..=12]);
+        set.insert(4..=5);
+        assert_eq!(ranges(&set), [1..=7, 10..=12]);
+        set.insert(0..=11);
+        assert_eq!(ranges(&set), [0..=12]);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(20..=19);
+        assert_eq!(set.total_len(), Some(13));
+        assert!(set.contains(12) && !set.contains(13) && !set.contains(-1));
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set = IntervalSet::from(0..=20);
+        set.remove(5..=9);
+        assert_eq!(ranges(&set), [0..=4, 10..=20]);
+        set.remove(3..=12);
+        assert_eq!(ranges(&set), [0..=2, 13..=20]);
+        set.remove(-5..=30);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet<i32> = [0..=4, 10..=14].into_iter().collect();
+        let b: IntervalSet<i32> = [3..=11, 20..=20].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), [0..=14, 20..=20]);
+        assert_eq!(ranges(&a.intersection(&b)), [3..=4, 10..=11]);
+        assert_eq!(ranges(&a.complement(-2..=12)), [-2..=-1, 5..=9]);
+        assert_eq!(ranges(&b.complement(0..=30)), [0..=2, 12..=19, 21..=30]);
+        assert!(a.intersection(&IntervalSet::from(5..=9)).is_empty());
+    }
+
+    #[test]
+    fn gaps_at_the_limits() {
+        let set = IntervalSet::from(0..=u8::MAX);
+        assert_eq!(set.gaps(0..=u8::MAX).count(), 0);
+        let set = IntervalSet::from(250..=u8::MAX);
+        assert_eq!(set.gaps(0..=u8::MAX).collect::<Vec<_>>(), [0..=249]);
+        assert_eq!(
+            IntervalSet::<u8>::new().gaps(3..=5).collect::<Vec<_>>(),
+            [3..=5]
+        );
+        assert_eq!(set.total_len(), Some(6));
+    }
+
+    #[test]
+    fn too_long_to_count() {
+        let everything = IntervalSet::<u8>::new().complement(0..=u8::MAX);
+        assert_eq!(everything.total_len(), None);
+        assert_eq!(IntervalSet::from(1..=u8::MAX).total_len(), Some(u8::MAX));
+        assert_eq!(IntervalSet::from(i8::MIN..=i8::MAX).total_len(), None);
+        assert_eq!(IntervalSet::from(-100..=26_i8).total_len(), Some(i8::MAX));
+        let split: IntervalSet<i8> = [-100..=0, 2..=100].into_iter().collect();
+        assert_eq!(split.total_len(), None);
+        assert_eq!(IntervalSet::<i8>::new().total_len(), Some(0));
+    }
+}
@@ -1,5 +1,20 @@
 use std::{fs::File, io::Read};
 
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+mod day;
+pub mod geometry;
+pub mod input;
+pub mod interval;
+pub mod ocr;
+pub mod parse;
+pub mod search;
+mod solution;
+
+pub use day::{print_answers, run_day, Day, PartFn};
+pub use solution::Solution;
+
 pub fn read_file(file_name: &str) -> anyhow::Result<String> {
     let mut contents = String::new();
     File::open(file_name)?.read_to_string(&mut contents)?;
@@ -0,0 +1,124 @@
+//! Reading the block letters some puzzles draw, in the 4x6 font they all
+//! share. Letters are 4 pixels wide and 6 tall, with a blank column between
+//! them; `#` is a lit pixel and anything else is dark.
+
+use anyhow::bail;
+
+use crate::AnyResult;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+// each letter takes up its width plus the gap after it
+const GLYPH_PITCH: usize = GLYPH_WIDTH + 1;
+
+// the letters seen so far in puzzle outputs
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read the letters drawn in `rows`, which must be six rows of pixels.
+///
+/// Fails on any glyph that isn't in the font, showing the glyph.
+pub fn read_letters<S: AsRef<str>>(rows: &[S]) -> AnyResult<String> {
+    if rows.len() != GLYPH_HEIGHT {
+        bail!(
+            "expected {GLYPH_HEIGHT} rows of pixels, found {}",
+            rows.len()
+        );
+    }
+    let pixels: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(|ch| ch == '#').collect())
+        .collect();
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let pixel = |row: usize, col: usize| pixels[row].get(col).copied().unwrap_or(false);
+
+    let mut letters = String::new();
+    for start in (0..width).step_by(GLYPH_PITCH) {
+        let glyph: [String; GLYPH_HEIGHT] = std::array::from_fn(|row| {
+            (start..start + GLYPH_WIDTH)
+                .map(|col| if pixel(row, col) { '#' } else { '.' })
+                .collect()
+        });
+        let letter = FONT
+            .iter()
+            .find(|(_, shape)| shape.iter().zip(&glyph).all(|(a, b)| a == b))
+            .map(|(letter, _)| *letter);
+
+        match letter {
+            Some(letter) => letters.push(letter),
+            None => bail!(
+                "unrecognised glyph {} at column {start}:\n{}",
+                letters.len() + 1,
+                glyph.join("\n")
+            ),
+        }
+    }
+    Ok(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(raster: &str) -> Vec<&str> {
+        raster.lines().collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let raster = "\
+            ####.####.#..#..##..#....###...##..###..\n\
+            #....#....#..#.#..#.#....#..#.#..#.#..#.\n\
+            ###..###..#..#.#....#....#..#.#..#.#..#.\n\
+            #....#....#..#.#.##.#....###..####.###..\n\
+            #....#....#..#.#..#.#....#....#..#.#....\n\
+            ####.#.....##...###.####.#....#..#.#....";
+        assert_eq!(read_letters(&rows(raster)).unwrap(), "EFUGLPAP");
+    }
+
+    #[test]
+    fn reads_every_letter() {
+        let raster: Vec<String> = (0..GLYPH_HEIGHT)
+            .map(|row| FONT.map(|(_, shape)| shape[row]).join("."))
+            .collect();
+        let expected: String = FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read_letters(&raster).unwrap(), expected);
+    }
+
+    #[test]
+    fn unknown_glyph_reported() {
+        let raster = "\
+            ####.#..#\n\
+            #....##.#\n\
+            ###..#.##\n\
+            #....#..#\n\
+            #....#..#\n\
+            ####.#..#";
+        let err = read_letters(&rows(raster)).unwrap_err().to_string();
+        assert!(err.starts_with("unrecognised glyph 2 at column 5"), "{err}");
+        assert!(err.ends_with("#..#"), "{err}");
+    }
+
+    #[test]
+    fn wrong_height_reported() {
+        assert!(read_letters(&["####"]).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Located errors for malformed puzzle input.
+
+use std::{fmt::Display, str::FromStr};
+
+/// A problem with the puzzle input, pointing at the offending text.
+///
+/// Lines and columns are 1-based; columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error about `span`, which should be a slice of `input`; its line
+    /// and column are worked out from where it sits in `input`.
+    pub fn at(input: &str, span: &str, message: impl Display) -> Self {
+        let offset = offset_within(input, span)
+            .or_else(|| input.find(span))
+            .unwrap_or(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: span.to_owned(),
+            message: message.to_string(),
+        }
+    }
+}
+
+fn offset_within(outer: &str, inner: &str) -> Option<usize> {
+    let start = outer.as_ptr() as usize;
+    let inner_start = inner.as_ptr() as usize;
+    (inner_start >= start && inner_start + inner.len() <= start + outer.len())
+        .then(|| inner_start - start)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}: '{}'",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The input being parsed, for reporting errors against it.
+///
+/// Parsers slice their fields out of the input as usual, and hand those
+/// slices back here to convert them or to report a problem with them.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Source { input }
+    }
+
+    pub fn error(&self, span: &str, message: impl Display) -> ParseError {
+        ParseError::at(self.input, span, message)
+    }
+
+    /// An error for input that stops before something expected.
+    pub fn end_error(&self, message: impl Display) -> ParseError {
+        self.error(&self.input[self.input.len()..], message)
+    }
+
+    /// Parse `span` with [`FromStr`], reporting failures at `span`.
+    pub fn parse<T>(&self, span: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        span.parse()
+            .map_err(|e| self.error(span, format!("invalid value ({e})")))
+    }
+
+    /// Unwrap `value`, or report `message` at `span` if it is missing.
+    pub fn require<T>(
+        &self,
+        value: Option<T>,
+        span: &str,
+        message: impl Display,
+    ) -> Result<T, ParseError> {
+        value.ok_or_else(|| self.error(span, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1,2\n3,x\nfoo bar\n";
+
+    #[test]
+    fn locates_slices() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let field = line.split(',').nth(1).unwrap();
+        let err = ParseError::at(INPUT, field, "bad number");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "x");
+        assert_eq!(err.to_string(), "line 2, column 3: bad number: 'x'");
+    }
+
+    #[test]
+    fn locates_copies_by_search() {
+        let err = ParseError::at(INPUT, &String::from("bar"), "unexpected");
+        assert_eq!((err.line, err.column), (3, 5));
+    }
+
+    #[test]
+    fn source_parse() {
+        let src = Source::new(INPUT);
+        let fields: Vec<&str> = INPUT.lines().flat_map(|l| l.split(',')).collect();
+        assert_eq!(src.parse::<i32>(fields[2]), Ok(3));
+
+        let err = src.parse::<i32>(fields[3]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(err.message.starts_with("invalid value"));
+
+        let err = src.require(None::<i32>, fields[4], "missing").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 1, "foo bar"));
+    }
+}
@@ -0,0 +1,300 @@
+//! Shortest-path searches over implicit graphs.
+//!
+//! Each search takes its start node(s), a successor function describing the
+//! graph, and a goal predicate. The search stops as soon as a goal node is
+//! settled; pass `|_| false` to explore everything reachable instead, for
+//! example to build a distance field.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num_traits::Zero;
+
+/// The outcome of a search: the cost to every node reached, how each was
+/// reached, and the goal node if one was found.
+///
+/// Once the goal is found the search stops, leaving nodes it had reached but
+/// not yet settled. Their costs and paths are only the cheapest found so far,
+/// an upper bound on the true cost; [`bfs`] is the exception, since with every
+/// edge costing 1 the first path it finds to a node is a cheapest one.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+impl<N: Clone + Eq + Hash, C: Copy> SearchResult<N, C> {
+    fn new() -> Self {
+        SearchResult {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The goal node that stopped the search, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest path to the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|g| self.cost(g))
+    }
+
+    /// The cheapest path to the goal, from a start node to the goal
+    /// inclusive.
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+
+    /// The cost of the cheapest path to `node`, or `None` if it wasn't
+    /// reached. If the search stopped at a goal before settling `node`, this
+    /// may be more than the true cheapest cost.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The cheapest path to `node`, from a start node to `node` inclusive,
+    /// with the same caveat as [`cost`](Self::cost).
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(parent) = self.parents.get(current) {
+            path.push(parent.clone());
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Costs to every node reached by the search, which are upper bounds for
+    /// any it hadn't settled when it stopped at a goal.
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+}
+
+/// Breadth-first search where every edge costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if result.costs.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(u) = queue.pop_front() {
+        if is_goal(&u) {
+            result.goal = Some(u);
+            break;
+        }
+        let next_cost = result.costs[&u] + 1;
+        for v in successors(&u) {
+            if let Entry::Vacant(entry) = result.costs.entry(v.clone()) {
+                entry.insert(next_cost);
+                result.parents.insert(v.clone(), u.clone());
+                queue.push_back(v);
+            }
+        }
+    }
+    result
+}
+
+/// Dijkstra's algorithm, for non-negative edge costs. The successor function
+/// yields each neighbour with the cost of the edge to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search. `heuristic` estimates the remaining cost to a goal and must
+/// never overestimate it, or the path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        result.costs.insert(start.clone(), C::zero());
+        queue.push(Queued {
+            estimate: heuristic(&start),
+            cost: C::zero(),
+            node: start,
+        });
+    }
+
+    while let Some(Queued { cost, node: u, .. }) = queue.pop() {
+        // skip stale entries; a cheaper route to `u` has already been settled
+        if cost > result.costs[&u] {
+            continue;
+        }
+        if is_goal(&u) {
+            result.goal = Some(u);
+            break;
+        }
+
+        for (v, edge_cost) in successors(&u) {
+            let alt = cost + edge_cost;
+            let improved = match result.costs.entry(v.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(alt);
+                    true
+                }
+                Entry::Occupied(mut entry) if alt < *entry.get() => {
+                    entry.insert(alt);
+                    true
+                }
+                Entry::Occupied(_) => false,
+            };
+            if improved {
+                result.parents.insert(v.clone(), u.clone());
+                queue.push(Queued {
+                    estimate: alt + heuristic(&v),
+                    cost: alt,
+                    node: v,
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Priority queue entry, ordered so that `BinaryHeap` pops the lowest
+/// estimate first.
+struct Queued<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<N, C: Ord> Eq for Queued<N, C> {}
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // prefer deeper nodes among equal estimates, which reach goals sooner
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // small weighted graph: a -1-> b -1-> c, a -5-> c, c -1-> d
+    fn weighted(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    // 1D number line, moving one step at a time
+    fn line(n: &i32) -> [i32; 2] {
+        [n - 1, n + 1]
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let result = bfs([0], line, |&n| n == 3);
+        assert_eq!(result.goal(), Some(&3));
+        assert_eq!(result.goal_cost(), Some(3));
+        assert_eq!(result.goal_path(), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn bfs_multiple_starts() {
+        let result = bfs([0, 10], line, |&n| n == 7);
+        assert_eq!(result.goal_path(), Some(vec![10, 9, 8, 7]));
+    }
+
+    #[test]
+    fn bfs_explores_everything_without_goal() {
+        let result = bfs([0], |&n: &i32| (n < 5).then_some(n + 1), |_| false);
+        assert_eq!(result.goal(), None);
+        assert_eq!(result.costs().len(), 6);
+        assert_eq!(result.cost(&5), Some(5));
+        assert_eq!(result.path_to(&2), Some(vec![0, 1, 2]));
+        assert_eq!(result.path_to(&6), None);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_path() {
+        let result = dijkstra(['a'], weighted, |&n| n == 'd');
+        assert_eq!(result.goal_cost(), Some(3));
+        assert_eq!(result.goal_path(), Some(vec!['a', 'b', 'c', 'd']));
+    }
+
+    #[test]
+    fn early_exit_leaves_frontier_upper_bounds() {
+        // stopping at b leaves c reached only directly from a, at 5 rather
+        // than 2 by way of b
+        let result = dijkstra(['a'], weighted, |&n| n == 'b');
+        assert_eq!(result.goal_cost(), Some(1));
+        assert_eq!(result.cost(&'c'), Some(5));
+        assert_eq!(result.path_to(&'c'), Some(vec!['a', 'c']));
+        assert_eq!(result.cost(&'d'), None);
+
+        let settled = dijkstra(['a'], weighted, |_| false);
+        assert_eq!(settled.cost(&'c'), Some(2));
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let goal = 7;
+        let result = astar(
+            [0],
+            |n: &i32| line(n).map(|m| (m, 1)),
+            |n| (goal - n).abs(),
+            |&n| n == goal,
+        );
+        assert_eq!(result.goal_cost(), Some(7));
+        // the heuristic keeps the search heading towards the goal
+        assert!(result.costs().len() < 12);
+    }
+}
@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use crate::AnyResult;
+
+/// Common shape for each day's solution: parse the puzzle input once, then
+/// answer both parts from the parsed form.
+///
+/// Implementors are usually a unit struct (e.g. `Day6`) that delegates to the
+/// day's own functions; see [`Day::new`](crate::Day::new) for the type-erased
+/// form used by the runner.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> AnyResult<Self::Input>;
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1>;
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2>;
+}
+
+/// Parse and solve part 1, formatting the answer.
+pub fn solve_part1<S: Solution>(input: &str) -> AnyResult<String> {
+    let parsed = S::parse(input)?;
+    Ok(S::part1(&parsed)?.to_string())
+}
+
+/// Parse and solve part 2, formatting the answer.
+pub fn solve_part2<S: Solution>(input: &str) -> AnyResult<String> {
+    let parsed = S::parse(input)?;
+    Ok(S::part2(&parsed)?.to_string())
+}
@@ -0,0 +1,132 @@
+use common::{parse::Source, AnyResult, Day, Solution};
+
+// note: `sums` is sorted in descending order by both of the parsing
+// functions below
+fn part1(sums: &[i32]) -> i32 {
+    sums[0]
+}
+
+fn part2(sums: &[i32]) -> i32 {
+    sums[0..3].iter().sum()
+}
+
+// note the `anyhow::Result` return for simple error handling; it'll take care
+// of mapping the Err types returned by the early breakout `?` operator to the `anyhow` type
+// for reporting, so this simplifies app development quite a bit.
+fn iterators(input: &str) -> anyhow::Result<Vec<i32>> {
+    let src = Source::new(input);
+
+    // read all lines into a vector
+    let lines: Vec<&str> = input.lines().collect();
+
+    // split the lines list into slices separated by empty lines
+    let groups = lines.split(|l| l.is_empty());
+
+    // map those groups of lines into sums for each group; summing and collecting
+    // into a `Result` stops at the first line that isn't a number
+    let mut sums: Vec<i32> = groups
+        .map(|grp| grp.iter().map(|s| src.parse::<i32>(s)).sum())
+        .collect::<Result<_, _>>()?;
+
+    // reverse sort
+    sums.sort_by_key(|x| -x);
+
+    // check we have sufficient items in the vec, since we're about
+    // to just do straight up indexing (and this would panic if the
+    // vec was too short...
+    if sums.len() < 3 {
+        anyhow::bail!("too few groups")
+    }
+
+    Ok(sums)
+}
+
+// same again, but written as a plain loop
+#[allow(dead_code)]
+fn imperative(input: &str) -> anyhow::Result<Vec<i32>> {
+    let src = Source::new(input);
+    let mut sum = 0;
+    let mut sums = Vec::new();
+    for l in input.lines() {
+        if l.is_empty() {
+            sums.push(sum);
+            sum = 0;
+        } else {
+            let val: i32 = src.parse(l)?;
+            sum += val;
+        }
+    }
+    // last group isn't followed by an empty line
+    sums.push(sum);
+
+    sums.sort_by_key(|x| -x);
+
+    if sums.len() < 3 {
+        anyhow::bail!("too few groups")
+    }
+
+    Ok(sums)
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        iterators(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day1>(1, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        1000
+        2000
+        3000
+
+        4000
+
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000
+    "};
+
+    #[test]
+    fn iterators_and_imperative_agree() {
+        let a = iterators(TEST_INPUT).unwrap();
+        let b = imperative(TEST_INPUT).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn part1_correct() {
+        let sums = iterators(TEST_INPUT).unwrap();
+        assert_eq!(part1(&sums), 24000);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let sums = iterators(TEST_INPUT).unwrap();
+        assert_eq!(part2(&sums), 45000);
+    }
+}
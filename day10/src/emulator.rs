@@ -0,0 +1,585 @@
+//! An emulator for the handheld's CPU, for stepping through programs and
+//! seeing what they do to the register cycle by cycle.
+//!
+//! Instructions come from an [`InstructionSet`], so new opcodes can be added
+//! with their own cycle costs. A [`Cpu`] runs a program one cycle at a time,
+//! can stop at breakpoints, evaluates watch expressions, and can record a
+//! [`Trace`] to write out as CSV or JSON.
+
+use std::{fmt::Display, io::Write};
+
+use anyhow::bail;
+use common::{parse::Source, AnyResult};
+use serde_json::json;
+
+/// The state of the CPU during a clock cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    pub clock: u64,
+    pub register: i64,
+}
+impl Default for Machine {
+    fn default() -> Self {
+        Self {
+            clock: 1,
+            register: 1,
+        }
+    }
+}
+
+/// What an instruction does, once its last cycle is complete.
+pub type Effect = fn(&mut Machine, &[i64]);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Opcode {
+    pub mnemonic: &'static str,
+    pub cycles: u64,
+    pub arity: usize,
+    pub effect: Effect,
+}
+
+/// The opcodes a program can use.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+}
+impl Default for InstructionSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+impl InstructionSet {
+    pub fn empty() -> Self {
+        InstructionSet { opcodes: vec![] }
+    }
+
+    /// `noop` and `addx`, as described in the puzzle.
+    pub fn standard() -> Self {
+        InstructionSet {
+            opcodes: vec![
+                Opcode {
+                    mnemonic: "noop",
+                    cycles: 1,
+                    arity: 0,
+                    effect: |_, _| {},
+                },
+                Opcode {
+                    mnemonic: "addx",
+                    cycles: 2,
+                    arity: 1,
+                    effect: |m, args| m.register += args[0],
+                },
+            ],
+        }
+    }
+
+    pub fn define(&mut self, opcode: Opcode) -> AnyResult<()> {
+        if self.lookup(opcode.mnemonic).is_some() {
+            bail!("opcode {} is already defined", opcode.mnemonic);
+        }
+        if opcode.cycles == 0 {
+            bail!("opcode {} must take at least one cycle", opcode.mnemonic);
+        }
+        self.opcodes.push(opcode);
+        Ok(())
+    }
+
+    fn lookup(&self, mnemonic: &str) -> Option<&Opcode> {
+        self.opcodes.iter().find(|op| op.mnemonic == mnemonic)
+    }
+
+    /// Parse a program of one instruction per line, each a mnemonic
+    /// followed by its integer arguments.
+    pub fn parse_program(&self, input: &str) -> AnyResult<Vec<Instruction>> {
+        let src = Source::new(input);
+        let mut program = vec![];
+        for line in input.lines() {
+            let mut fields = line.split_whitespace();
+            let mnemonic = fields.next().unwrap_or(line);
+            let opcode =
+                *src.require(self.lookup(mnemonic), mnemonic, "unrecognised instruction")?;
+
+            let mut args = vec![];
+            for field in fields {
+                if args.len() == opcode.arity {
+                    return Err(src.error(field, "unexpected trailing argument").into());
+                }
+                args.push(src.parse(field)?);
+            }
+            if args.len() < opcode.arity {
+                let message = format!("{mnemonic} takes {} argument(s)", opcode.arity);
+                return Err(src.error(line, message).into());
+            }
+            program.push(Instruction { opcode, args });
+        }
+        Ok(program)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    opcode: Opcode,
+    args: Vec<i64>,
+}
+impl Instruction {
+    pub fn cycles(&self) -> u64 {
+        self.opcode.cycles
+    }
+}
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.opcode.mnemonic)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+/// When a breakpoint stops the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    Clock(u64),
+    RegisterEquals(i64),
+    RegisterAtLeast(i64),
+    RegisterAtMost(i64),
+}
+impl Condition {
+    fn holds(&self, machine: &Machine) -> bool {
+        match *self {
+            Condition::Clock(clock) => machine.clock == clock,
+            Condition::RegisterEquals(v) => machine.register == v,
+            Condition::RegisterAtLeast(v) => machine.register >= v,
+            Condition::RegisterAtMost(v) => machine.register <= v,
+        }
+    }
+}
+
+/// Why [`Cpu::run`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// The breakpoint with this index matched.
+    Breakpoint(usize),
+}
+
+struct Watch {
+    name: String,
+    expr: Box<dyn Fn(&Machine) -> i64>,
+}
+
+pub struct Cpu<'p> {
+    program: &'p [Instruction],
+    pc: usize,
+    // cycles spent so far on the current instruction
+    busy: u64,
+    machine: Machine,
+    breakpoints: Vec<Condition>,
+    // the clock when run last stopped at a breakpoint, so that running again
+    // carries on past it
+    stopped_at: Option<u64>,
+    watches: Vec<Watch>,
+    trace: Option<Trace>,
+}
+impl<'p> Cpu<'p> {
+    pub fn new(program: &'p [Instruction]) -> Self {
+        Self::with_machine(program, Machine::default())
+    }
+
+    pub fn with_machine(program: &'p [Instruction], machine: Machine) -> Self {
+        Cpu {
+            program,
+            pc: 0,
+            busy: 0,
+            machine,
+            breakpoints: vec![],
+            stopped_at: None,
+            watches: vec![],
+            trace: None,
+        }
+    }
+
+    pub fn machine(&self) -> Machine {
+        self.machine
+    }
+
+    /// The instruction being executed this cycle, if the program hasn't
+    /// finished.
+    pub fn current_instruction(&self) -> Option<&'p Instruction> {
+        self.program.get(self.pc)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Stop [`run`](Self::run) before any cycle during which `condition`
+    /// holds, or once the program has finished if it holds then. Returns the
+    /// index reported in [`Stop::Breakpoint`].
+    pub fn add_breakpoint(&mut self, condition: Condition) -> usize {
+        self.breakpoints.push(condition);
+        self.breakpoints.len() - 1
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Evaluate `expr` every cycle, under `name`. Watches are shown by
+    /// [`watch_values`](Self::watch_values) and recorded in the trace, so
+    /// they must all be added before recording starts, and each needs a name
+    /// of its own that isn't one of the trace's other columns.
+    pub fn watch(&mut self, name: &str, expr: impl Fn(&Machine) -> i64 + 'static) -> AnyResult<()> {
+        if self.trace.is_some() {
+            bail!("can't watch {name} once the trace has started");
+        }
+        if TRACE_COLUMNS.contains(&name) || self.watches.iter().any(|w| w.name == name) {
+            bail!("there's already a trace column called {name}");
+        }
+        self.watches.push(Watch {
+            name: name.to_owned(),
+            expr: Box::new(expr),
+        });
+        Ok(())
+    }
+
+    pub fn watch_values(&self) -> Vec<(&str, i64)> {
+        self.watches
+            .iter()
+            .map(|w| (w.name.as_str(), (w.expr)(&self.machine)))
+            .collect()
+    }
+
+    /// Start recording every cycle from here on, with a column for each
+    /// watch.
+    pub fn record_trace(&mut self) {
+        self.trace.get_or_insert_with(|| Trace {
+            watches: self.watches.iter().map(|w| w.name.clone()).collect(),
+            rows: vec![],
+        });
+    }
+
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Complete one clock cycle. Returns false, doing nothing, once the
+    /// program has finished.
+    pub fn step(&mut self) -> bool {
+        let Some(instruction) = self.current_instruction() else {
+            return false;
+        };
+        let row = self.trace.is_some().then(|| TraceRow {
+            clock: self.machine.clock,
+            register: self.machine.register,
+            instruction: instruction.to_string(),
+            watches: self.watch_values().into_iter().map(|(_, v)| v).collect(),
+        });
+        if let (Some(trace), Some(row)) = (&mut self.trace, row) {
+            trace.rows.push(row);
+        }
+
+        self.busy += 1;
+        if self.busy == instruction.cycles() {
+            (instruction.opcode.effect)(&mut self.machine, &instruction.args);
+            self.pc += 1;
+            self.busy = 0;
+        }
+        self.machine.clock += 1;
+        true
+    }
+
+    /// Step until the program finishes or a breakpoint matches.
+    pub fn run(&mut self) -> Stop {
+        let mut resuming = self.stopped_at == Some(self.machine.clock);
+        loop {
+            let hit = self.breakpoints.iter().position(|c| c.holds(&self.machine));
+            match hit {
+                Some(index) if !resuming => {
+                    self.stopped_at = Some(self.machine.clock);
+                    return Stop::Breakpoint(index);
+                }
+                _ => {}
+            }
+            resuming = false;
+            if !self.step() {
+                return Stop::Halted;
+            }
+        }
+    }
+}
+
+/// The columns every trace has, before those for the watches.
+const TRACE_COLUMNS: [&str; 3] = ["clock", "register", "instruction"];
+
+/// The state during every cycle the CPU ran while recording.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    watches: Vec<String>,
+    rows: Vec<TraceRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRow {
+    pub clock: u64,
+    pub register: i64,
+    pub instruction: String,
+    /// Values of the watches, in the order they were added.
+    pub watches: Vec<i64>,
+}
+
+impl Trace {
+    pub fn rows(&self) -> &[TraceRow] {
+        &self.rows
+    }
+
+    /// One row per cycle, with a column for each watch.
+    pub fn write_csv(&self, mut out: impl Write) -> AnyResult<()> {
+        let mut header = TRACE_COLUMNS.map(String::from).to_vec();
+        header.extend(self.watches.iter().map(|w| csv_field(w)));
+        writeln!(out, "{}", header.join(","))?;
+
+        for row in &self.rows {
+            let instruction = csv_field(&row.instruction);
+            write!(out, "{},{},{instruction}", row.clock, row.register)?;
+            for value in &row.watches {
+                write!(out, ",{value}")?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// An array with an object per cycle, with a key for each watch.
+    pub fn write_json(&self, out: impl Write) -> AnyResult<()> {
+        let rows: Vec<_> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut object = json!({
+                    "clock": row.clock,
+                    "register": row.register,
+                    "instruction": row.instruction,
+                });
+                for (name, value) in self.watches.iter().zip(&row.watches) {
+                    object[name] = json!(value);
+                }
+                object
+            })
+            .collect();
+        serde_json::to_writer_pretty(out, &rows)?;
+        Ok(())
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const PROGRAM: &str = indoc! {"
+        noop
+        addx 3
+        addx -5
+    "};
+
+    fn program() -> Vec<Instruction> {
+        InstructionSet::standard().parse_program(PROGRAM).unwrap()
+    }
+
+    #[test]
+    fn steps_cycle_by_cycle() {
+        let program = program();
+        let mut cpu = Cpu::new(&program);
+        let mut registers = vec![cpu.machine().register];
+        while cpu.step() {
+            registers.push(cpu.machine().register);
+        }
+        assert_eq!(registers, [1, 1, 1, 4, 4, -1]);
+        assert!(cpu.is_halted());
+        assert_eq!(cpu.machine().clock, 6);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let program = program();
+        let mut cpu = Cpu::new(&program);
+        let clock = cpu.add_breakpoint(Condition::Clock(3));
+        let low = cpu.add_breakpoint(Condition::RegisterAtMost(0));
+
+        assert_eq!(cpu.run(), Stop::Breakpoint(clock));
+        assert_eq!(
+            cpu.machine(),
+            Machine {
+                clock: 3,
+                register: 1
+            }
+        );
+        assert_eq!(cpu.current_instruction().unwrap().to_string(), "addx 3");
+
+        assert_eq!(cpu.run(), Stop::Breakpoint(low));
+        assert_eq!(
+            cpu.machine(),
+            Machine {
+                clock: 6,
+                register: -1
+            }
+        );
+        assert_eq!(cpu.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn breakpoint_on_the_first_cycle() {
+        let program = program();
+        let mut cpu = Cpu::new(&program);
+        let first = cpu.add_breakpoint(Condition::Clock(1));
+        let one = cpu.add_breakpoint(Condition::RegisterEquals(1));
+
+        assert_eq!(cpu.run(), Stop::Breakpoint(first));
+        assert_eq!(cpu.machine(), Machine::default());
+        assert_eq!(cpu.current_instruction().unwrap().to_string(), "noop");
+
+        // the register is still 1 in the next cycle
+        assert_eq!(cpu.run(), Stop::Breakpoint(one));
+        assert_eq!(cpu.machine().clock, 2);
+        cpu.clear_breakpoints();
+        assert_eq!(cpu.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn watches_fixed_once_tracing() {
+        let program = program();
+        let mut cpu = Cpu::new(&program);
+        assert!(cpu.watch("clock", |m| m.clock as i64).is_err());
+        assert!(cpu.watch("register", |m| m.register).is_err());
+        cpu.watch("double", |m| 2 * m.register).unwrap();
+        assert!(cpu.watch("double", |m| 3 * m.register).is_err());
+
+        cpu.record_trace();
+        assert!(cpu.watch("triple", |m| 3 * m.register).is_err());
+        cpu.run();
+
+        let mut csv = vec![];
+        cpu.trace().unwrap().write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.lines().all(|l| l.split(',').count() == 4));
+    }
+
+    #[test]
+    fn custom_opcodes() {
+        let mut set = InstructionSet::standard();
+        set.define(Opcode {
+            mnemonic: "mulx",
+            cycles: 3,
+            arity: 1,
+            effect: |m, args| m.register *= args[0],
+        })
+        .unwrap();
+        assert!(set
+            .define(Opcode {
+                cycles: 1,
+                ..set.opcodes[0]
+            })
+            .is_err());
+
+        let program = set.parse_program("addx 2\nmulx 5\n").unwrap();
+        let mut cpu = Cpu::new(&program);
+        assert_eq!(cpu.run(), Stop::Halted);
+        assert_eq!(
+            cpu.machine(),
+            Machine {
+                clock: 6,
+                register: 15
+            }
+        );
+    }
+
+    #[test]
+    fn bad_instructions_located() {
+        let set = InstructionSet::standard();
+        let locate = |input: &str| {
+            let err = set.parse_program(input).unwrap_err();
+            let err = err.downcast::<common::parse::ParseError>().unwrap();
+            (err.line, err.column, err.text)
+        };
+        assert_eq!(locate("noop\nmulx 2\n"), (2, 1, "mulx".to_owned()));
+        assert_eq!(locate("addx\n"), (1, 1, "addx".to_owned()));
+        assert_eq!(locate("noop 1\n"), (1, 6, "1".to_owned()));
+    }
+
+    #[test]
+    fn traces_watches() {
+        let program = program();
+        let mut cpu = Cpu::new(&program);
+        cpu.watch("signal", |m| m.clock as i64 * m.register)
+            .unwrap();
+        cpu.record_trace();
+        cpu.run();
+
+        let trace = cpu.trace().unwrap();
+        assert_eq!(trace.rows().len(), 5);
+        assert_eq!(
+            trace.rows()[3],
+            TraceRow {
+                clock: 4,
+                register: 4,
+                instruction: "addx -5".to_owned(),
+                watches: vec![16],
+            }
+        );
+
+        let mut csv = vec![];
+        trace.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv.lines().take(3).collect::<Vec<_>>(),
+            [
+                "clock,register,instruction,signal",
+                "1,1,noop,1",
+                "2,1,addx 3,2"
+            ]
+        );
+
+        let mut json = vec![];
+        trace.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json[4]["register"], 4);
+        assert_eq!(json[4]["signal"], 20);
+    }
+
+    #[test]
+    fn csv_fields_escaped() {
+        let mut set = InstructionSet::standard();
+        set.define(Opcode {
+            mnemonic: "say,\"hi\"",
+            cycles: 1,
+            arity: 0,
+            effect: |_, _| {},
+        })
+        .unwrap();
+        let program = set.parse_program("say,\"hi\"\n").unwrap();
+        let mut cpu = Cpu::new(&program);
+        cpu.watch("a,b", |m| m.register).unwrap();
+        cpu.record_trace();
+        cpu.run();
+
+        let mut csv = vec![];
+        cpu.trace().unwrap().write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "clock,register,instruction,\"a,b\"",
+                "1,1,\"say,\"\"hi\"\"\",1"
+            ]
+        );
+    }
+}
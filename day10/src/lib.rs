@@ -0,0 +1,165 @@
+pub mod emulator;
+
+use common::{ocr, AnyResult, Day, Solution};
+use emulator::{Cpu, Instruction, InstructionSet, Machine};
+
+fn execute(init: Machine, instructions: &[Instruction]) -> Vec<Machine> {
+    let mut cpu = Cpu::with_machine(instructions, init);
+    let mut observations = vec![cpu.machine()];
+    while cpu.step() {
+        observations.push(cpu.machine());
+    }
+    observations
+}
+
+fn predicate_20_then_every_40(clock: u64) -> bool {
+    match clock {
+        20 => true,
+        x if x > 20 && (x - 20) % 40 == 0 => true,
+        _ => false,
+    }
+}
+
+fn part1(instructions: &[Instruction]) -> i64 {
+    let mut observations = execute(Machine::default(), instructions);
+    observations.retain(|m| predicate_20_then_every_40(m.clock));
+    observations
+        .iter()
+        .map(|m| m.clock as i64 * m.register)
+        .sum()
+}
+
+fn part2(instructions: &[Instruction]) -> Vec<String> {
+    let observations = execute(Machine::default(), instructions);
+    observations
+        .chunks_exact(40)
+        .map(|chunk| {
+            let mut line = String::new();
+            for (col, m) in chunk.iter().enumerate() {
+                let column = col as i64 + 1; // column is 1-indexed
+                let sprite_range = m.register..m.register + 3;
+                let ch = if sprite_range.contains(&column) {
+                    '#'
+                } else {
+                    '.'
+                };
+                line.push(ch);
+            }
+
+            line
+        })
+        .collect()
+}
+
+fn parse_input(inputs: &str) -> anyhow::Result<Vec<Instruction>> {
+    InstructionSet::standard().parse_program(inputs)
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        ocr::read_letters(&part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day10>(10, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const BASIC_INPUT: &str = indoc! {"
+        noop
+        addx 3
+        addx -5
+    "};
+
+    const TEST_INPUT: &str = include_str!("../input_test.txt");
+
+    #[test]
+    fn basic_input_execution() {
+        let instructions = parse_input(BASIC_INPUT).unwrap();
+        let observations = execute(Machine::default(), &instructions);
+        let register_vals: Vec<_> = observations.iter().map(|m| m.register).collect();
+        // cycles
+        // 1 -> 1 (During the first cycle, X is 1)
+        // 2 -> 1 (During the second cycle, X is still 1)
+        // 3 -> 1 (During the third cycle, X is still 1)
+        // 4 -> 4 (During the fourth cycle, X is still 4)
+        // 5 -> 4 (During the fifth cycle, X is still 4)
+        // 6 -> -1 (After the fifth cycle, .. setting X to -1.)
+        assert_eq!(register_vals, [1, 1, 1, 4, 4, -1])
+    }
+
+    #[test]
+    fn test_input_execution_partial() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let mut observations = execute(Machine::default(), &instructions);
+        observations.retain(|m| predicate_20_then_every_40(m.clock));
+
+        let expected = [
+            Machine {
+                clock: 20,
+                register: 21,
+            },
+            Machine {
+                clock: 60,
+                register: 19,
+            },
+            Machine {
+                clock: 100,
+                register: 18,
+            },
+            Machine {
+                clock: 140,
+                register: 21,
+            },
+            Machine {
+                clock: 180,
+                register: 16,
+            },
+            Machine {
+                clock: 220,
+                register: 18,
+            },
+        ];
+        assert_eq!(observations, expected);
+    }
+
+    #[test]
+    fn part1_correct() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&instructions);
+        assert_eq!(res, 13140);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let instructions = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&instructions);
+        let expected = indoc! {"
+            ##..##..##..##..##..##..##..##..##..##..
+            ###...###...###...###...###...###...###.
+            ####....####....####....####....####....
+            #####.....#####.....#####.....#####.....
+            ######......######......######......####
+            #######.......#######.......#######.....
+        "};
+
+        let expected_lines: Vec<String> = expected.lines().map(|l| l.to_string()).collect();
+        assert_eq!(res, expected_lines);
+    }
+}
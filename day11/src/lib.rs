@@ -0,0 +1,285 @@
+use common::{
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+use regex::Regex;
+
+#[derive(Debug, Copy, Clone)]
+enum Operation {
+    AddConst(i64),
+    MulConst(i64),
+    Square,
+}
+impl Operation {
+    fn calculate_new(&self, old: &i64) -> i64 {
+        match self {
+            Operation::AddConst(a) => old + a,
+            Operation::MulConst(a) => old * a,
+            Operation::Square => old * old,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    operation: Operation,
+    test_divisible_by: i64,
+    test_true: usize,
+    test_false: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    monkeys: Vec<Monkey>,
+    holding_items: Vec<Vec<i64>>,
+    inspection_counts: Vec<usize>,
+}
+
+impl Simulation {
+    fn simulation_part1(&mut self, num_rounds: usize) {
+        for _ in 0..num_rounds {
+            self.simulation_round(|n| n / 3);
+        }
+    }
+
+    fn simulation_part2(&mut self, num_rounds: usize) {
+        let global_modulus: i64 = self.monkeys.iter().map(|m| m.test_divisible_by).product();
+        for _ in 0..num_rounds {
+            self.simulation_round(|n| n % global_modulus);
+        }
+    }
+
+    fn simulation_round<F: Fn(i64) -> i64>(&mut self, post_op: F) {
+        let length = self.monkeys.len();
+        for i in 0..length {
+            let monkey = &mut self.monkeys[i];
+
+            // take all items for this monkey, replacing them with an empty vector
+            let mut items = vec![];
+            std::mem::swap(&mut self.holding_items[i], &mut items);
+
+            // increase inspection counts for this monkey
+            self.inspection_counts[i] += items.len();
+
+            // give the items to the other monkeys according to the rules
+            for item in items.iter() {
+                let new_val = monkey.operation.calculate_new(item);
+                let new_val = post_op(new_val);
+                let dest_monkey = if new_val % monkey.test_divisible_by == 0.into() {
+                    monkey.test_true
+                } else {
+                    monkey.test_false
+                };
+
+                // give new item to destination monkey
+                self.holding_items[dest_monkey].push(new_val);
+            }
+        }
+    }
+}
+
+fn parse_input(inputs: &str) -> anyhow::Result<Simulation> {
+    let src = Source::new(inputs);
+    let re_number = Regex::new(r#"\d+"#)?;
+    let re_items = Regex::new(r#"^\s*Starting items:((?: \d+,?)*)$"#)?;
+    let re_operation = Regex::new(r#"^\s*Operation: new = old ([+*]) (\d+|old)$"#)?;
+    let re_test = Regex::new(r#"^\s*Test: divisible by (\d+)$"#)?;
+    let re_throw = Regex::new(r#"^\s*If (true|false): throw to monkey (\d+)$"#)?;
+
+    let mut monkeys = vec![];
+    let mut holding_items = vec![];
+    let mut targets = vec![];
+
+    let lines: Vec<_> = inputs.lines().collect();
+    for block in lines.split(|l| l.is_empty()) {
+        // the field on line `i` of the block matching `re`, reported against
+        // the last line we have if the block stops early
+        let field = |i: usize, re: &Regex, expected: &str| -> Result<_, ParseError> {
+            let line = block.get(i).copied();
+            let line = src.require(line, block.last().copied().unwrap_or(""), expected)?;
+            src.require(re.captures(line), line, format!("expected {expected}"))
+        };
+
+        let starting_items = {
+            let cap = field(1, &re_items, "'Starting items: ...'")?;
+            let mut items = vec![];
+            for m in re_number.find_iter(cap.get(1).unwrap().as_str()) {
+                items.push(src.parse(m.as_str())?);
+            }
+            items
+        };
+
+        let operation = {
+            let cap = field(2, &re_operation, "'Operation: new = old <op> <operand>'")?;
+            let operand = cap.get(2).unwrap().as_str();
+            match (&cap[1], operand) {
+                ("*", "old") => Operation::Square,
+                ("*", num) => Operation::MulConst(src.parse(num)?),
+                (_, "old") => {
+                    return Err(src.error(operand, "only 'old * old' is supported").into())
+                }
+                (_, num) => Operation::AddConst(src.parse(num)?),
+            }
+        };
+
+        let test_divisible = {
+            let cap = field(3, &re_test, "'Test: divisible by N'")?;
+            let divisor = cap.get(1).unwrap().as_str();
+            match src.parse(divisor)? {
+                0 => {
+                    return Err(src
+                        .error(divisor, "cannot test divisibility by zero")
+                        .into())
+                }
+                n => n,
+            }
+        };
+
+        let mut throw_target = |i: usize, branch: &str| -> Result<usize, ParseError> {
+            let cap = field(i, &re_throw, &format!("'If {branch}: throw to monkey N'"))?;
+            if &cap[1] != branch {
+                return Err(src.error(cap.get(1).unwrap().as_str(), format!("expected '{branch}'")));
+            }
+            let target = cap.get(2).unwrap().as_str();
+            targets.push(target);
+            src.parse(target)
+        };
+        let test_true = throw_target(4, "true")?;
+        let test_false = throw_target(5, "false")?;
+
+        let monkey = Monkey {
+            operation,
+            test_divisible_by: test_divisible,
+            test_true,
+            test_false,
+        };
+
+        monkeys.push(monkey);
+        holding_items.push(starting_items);
+    }
+
+    // every monkey must throw to one that exists
+    for target in targets {
+        if src.parse::<usize>(target)? >= monkeys.len() {
+            return Err(src.error(target, "no such monkey").into());
+        }
+    }
+
+    let inspection_counts = vec![0; monkeys.len()];
+
+    Ok(Simulation {
+        monkeys,
+        holding_items,
+        inspection_counts,
+    })
+}
+
+fn part1(simulation: &Simulation) -> usize {
+    let mut simulation = simulation.clone();
+    simulation.simulation_part1(20);
+
+    let mut counts = simulation.inspection_counts;
+    counts.sort();
+    counts.iter().rev().take(2).product()
+}
+
+fn part2(simulation: &Simulation) -> usize {
+    let mut simulation = simulation.clone();
+    simulation.simulation_part2(10_000);
+
+    let mut counts = simulation.inspection_counts;
+    counts.sort();
+    counts.iter().rev().take(2).product()
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+    type Input = Simulation;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day11>(11, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        Monkey 0:
+        Starting items: 79, 98
+        Operation: new = old * 19
+        Test: divisible by 23
+            If true: throw to monkey 2
+            If false: throw to monkey 3
+
+        Monkey 1:
+        Starting items: 54, 65, 75, 74
+        Operation: new = old + 6
+        Test: divisible by 19
+            If true: throw to monkey 2
+            If false: throw to monkey 0
+
+        Monkey 2:
+        Starting items: 79, 60, 97
+        Operation: new = old * old
+        Test: divisible by 13
+            If true: throw to monkey 1
+            If false: throw to monkey 3
+
+        Monkey 3:
+        Starting items: 74
+        Operation: new = old + 3
+        Test: divisible by 17
+            If true: throw to monkey 0
+            If false: throw to monkey 1
+    "};
+
+    #[test]
+    fn basic_input_execution() {
+        parse_input(TEST_INPUT).unwrap();
+    }
+
+    #[test]
+    fn simulate_one_part1() {
+        let mut sim = parse_input(TEST_INPUT).unwrap();
+        sim.simulation_part1(20);
+        assert_eq!(sim.inspection_counts, [101, 95, 7, 105])
+    }
+
+    #[test]
+    fn part1_correct() {
+        let sim = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&sim);
+        assert_eq!(res, 10605);
+    }
+
+    #[test]
+    fn simulate_one_part2() {
+        let mut sim = parse_input(TEST_INPUT).unwrap();
+        sim.simulation_part2(10_000);
+        assert_eq!(sim.inspection_counts, [52166, 47830, 1938, 52013])
+    }
+
+    #[test]
+    fn unknown_monkey_located() {
+        let input =
+            TEST_INPUT.replace("If false: throw to monkey 0", "If false: throw to monkey 7");
+        let err = parse_input(&input).unwrap_err();
+        let err = err.downcast_ref::<common::parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (13, 31, "7"));
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::bail;
+use common::{
+    geometry::{self, Grid},
+    parse::Source,
+    search::bfs,
+    AnyResult, Day, OptionAnyhow, Solution,
+};
+
+type Point = geometry::Point<isize>;
+
+#[derive(Debug)]
+pub struct Problem {
+    start: Point,
+    destination: Point,
+    grid: Grid<i32>,
+}
+
+fn parse_input(inputs: &str) -> anyhow::Result<Problem> {
+    let src = Source::new(inputs);
+    let chars = Grid::parse_chars(inputs, |ch| match ch {
+        'a'..='z' | 'S' | 'E' => Ok(ch),
+        _ => bail!("expected a height a-z, or S or E"),
+    })?;
+    let start = chars
+        .position(|&ch| ch == 'S')
+        .ok_or_else(|| src.end_error("missing start 'S'"))?;
+    let destination = chars
+        .position(|&ch| ch == 'E')
+        .ok_or_else(|| src.end_error("missing end 'E'"))?;
+
+    let grid = chars.map(|&ch| {
+        let ch = match ch {
+            'S' => 'a',
+            'E' => 'z',
+            ch => ch,
+        };
+        ch as i32 - 'a' as i32
+    });
+
+    Ok(Problem {
+        start,
+        destination,
+        grid,
+    })
+}
+
+fn valid_moves(grid: &Grid<i32>, current: Point) -> impl Iterator<Item = Point> + '_ {
+    let current_height = grid[current];
+    grid.neighbours4(current)
+        .filter(move |(_, h)| (*h - 1) <= current_height)
+        .map(|(p, _)| p)
+}
+
+// the moves that arrive at `current`: the reverse of `valid_moves`, so from
+// `current` we can step down at most one level, or up any amount
+fn reverse_moves(grid: &Grid<i32>, current: Point) -> impl Iterator<Item = Point> + '_ {
+    grid.neighbours4(current)
+        .map(|(p, _)| p)
+        .filter(move |&p| valid_moves(grid, p).any(|q| q == current))
+}
+
+/// The number of steps from every point to the destination, or `None` where
+/// the destination can't be reached. Every step costs the same, so this is a
+/// single breadth-first search backwards from the destination.
+pub fn distance_field(problem: &Problem) -> Grid<Option<usize>> {
+    let grid = &problem.grid;
+    let reached = bfs(
+        [problem.destination],
+        |&u| reverse_moves(grid, u),
+        |_| false,
+    );
+
+    let mut field = Grid::new(grid.width(), grid.height(), None);
+    for (&p, &steps) in reached.costs() {
+        field[p] = Some(steps);
+    }
+    field
+}
+
+fn part1(problem: &Problem) -> Option<usize> {
+    distance_field(problem)[problem.start]
+}
+
+// the nearest of all the lowest points
+fn part2(problem: &Problem) -> Option<usize> {
+    let field = distance_field(problem);
+    problem
+        .grid
+        .iter()
+        .filter(|(_, &h)| h == 0)
+        .filter_map(|(p, _): (Point, _)| field[p])
+        .min()
+}
+
+pub struct Day12;
+impl Solution for Day12 {
+    type Input = Problem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(input).ok_anyhow()
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input).ok_anyhow()
+    }
+}
+
+pub const DAY: Day = Day::new::<Day12>(12, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        Sabqponm
+        abcryxxl
+        accszExk
+        acctuvwj
+        abdefghi
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        parse_input(TEST_INPUT).unwrap();
+    }
+
+    #[test]
+    fn part1_correct() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let solution = part1(&problem).unwrap();
+        assert_eq!(solution, 31);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let solution = part2(&problem).unwrap();
+        assert_eq!(solution, 29);
+    }
+
+    #[test]
+    fn distance_field_correct() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let field = distance_field(&problem);
+        assert_eq!(field[problem.destination], Some(0));
+        assert_eq!(field[problem.start], Some(31));
+
+        // every reachable point is one step further than its best next move
+        for (p, steps) in field.iter::<isize>() {
+            if let Some(steps) = steps.filter(|&s| s > 0) {
+                let best = valid_moves(&problem.grid, p).filter_map(|q| field[q]).min();
+                assert_eq!(best, Some(steps - 1), "at {p:?}");
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::{all_consuming, map, recognize},
+    combinator::{all_consuming, cut, map},
     multi::separated_list0,
-    sequence::delimited,
+    sequence::{preceded, terminated},
     IResult,
 };
 
+use common::parse::{ParseError, Source};
+
 use crate::Value;
 
 pub fn parse_value(s: &str) -> IResult<&str, Value> {
@@ -15,11 +17,10 @@ pub fn parse_value(s: &str) -> IResult<&str, Value> {
 }
 
 pub fn parse_parens(s: &str) -> IResult<&str, Value> {
-    let start = recognize(tag("["));
-    let end = recognize(tag("]"));
     let elements = separated_list0(tag(","), parse_value);
     let mapped = map(elements, Value::List);
-    delimited(start, mapped, end)(s)
+    // once inside a list, don't backtrack, so errors point into the list
+    preceded(tag("["), cut(terminated(mapped, tag("]"))))(s)
 }
 
 pub fn parse_complete_expression(s: &str) -> IResult<&str, Value> {
@@ -30,9 +31,13 @@ pub fn parse_number(i: &str) -> IResult<&str, Value> {
     map(nom::character::complete::i32, Value::Literal)(i)
 }
 
-pub fn parse(input: &str) -> anyhow::Result<Value> {
-    let (_, expr) = parse_complete_expression(input).map_err(|e| e.to_owned())?;
-    Ok(expr)
+/// Parse the packet on `line`, reporting errors where parsing stopped.
+pub fn parse(src: Source, line: &str) -> Result<Value, ParseError> {
+    match parse_complete_expression(line) {
+        Ok((_, expr)) => Ok(expr),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(src.error(e.input, "invalid packet")),
+        Err(nom::Err::Incomplete(_)) => Err(src.error(line, "incomplete packet")),
+    }
 }
 
 #[cfg(test)]
@@ -42,17 +47,24 @@ mod tests {
     #[test]
     fn parse_basic_list() {
         let s = "[1,2,3]";
-        let res = parse(s).unwrap();
+        let res = parse(Source::new(s), s).unwrap();
         assert_eq!(res, vec![1.into(), 2.into(), 3.into()].into());
     }
 
     #[test]
     fn parse_nested_list() {
         let s = "[1,[2,3],4]";
-        let res = parse(s).unwrap();
+        let res = parse(Source::new(s), s).unwrap();
         assert_eq!(
             res,
             vec![1.into(), vec![2.into(), 3.into()].into(), 4.into()].into()
         );
     }
+
+    #[test]
+    fn parse_error_located() {
+        let s = "[1,[2;3],4]";
+        let err = parse(Source::new(s), s).unwrap_err();
+        assert_eq!(err.column, 6);
+    }
 }
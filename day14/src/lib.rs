@@ -1,115 +1,233 @@
-use std::{
-    fmt::Display,
-    ops::{Add, Sub},
+use std::fmt::Display;
+
+use common::{
+    geometry::{self, Grid},
+    parse::{ParseError, Source},
+    *,
 };
+use GridSquare::*;
 
-#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
-pub struct Point(pub isize, pub isize);
-impl Add for Point {
-    type Output = Point;
+pub type Point = geometry::Point<isize>;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Point(self.0 + rhs.0, self.1 + rhs.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridSquare {
+    Blank,
+    Rock,
+    Sand,
+}
+impl Display for GridSquare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GridSquare::Blank => ".",
+                GridSquare::Rock => "#",
+                GridSquare::Sand => "o",
+            }
+        )
     }
 }
-impl Sub for Point {
-    type Output = Point;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Point(self.0 - rhs.0, self.1 - rhs.1)
-    }
+#[derive(Debug, Clone)]
+pub struct Problem {
+    grid: Grid<GridSquare>,
+    sand_origin: Point,
+    max_y: isize,
 }
-impl Point {
-    pub fn signum(&self) -> Point {
-        Point(self.0.signum(), self.1.signum())
+impl Problem {
+    // drop sand and find resting location, None if we fall off the grid
+    fn drop_sand(&self, floor: Option<isize>) -> Option<Point> {
+        let delta_below = Point::new(0, 1);
+        let delta_left = Point::new(-1, 1);
+        let delta_right = Point::new(1, 1);
+
+        let mut cur = self.sand_origin;
+        loop {
+            // stop at floor if specified
+            if let Some(floor) = floor {
+                if cur.y + 1 == floor {
+                    return Some(cur);
+                }
+            }
+
+            // check move down
+            let point_below = cur + delta_below;
+            let below = self.grid.get(point_below)?;
+            if *below == Blank {
+                cur = point_below;
+                continue;
+            }
+
+            // check move left and right
+            let point_left = cur + delta_left;
+            let left = self.grid.get(point_left)?;
+            if *left == Blank {
+                cur = point_left;
+                continue;
+            }
+
+            let point_right = cur + delta_right;
+            let right = self.grid.get(point_right)?;
+            if *right == Blank {
+                cur = point_right;
+                continue;
+            }
+
+            // no more moves left; sand grain comes to rest here
+            return Some(cur);
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum Dir {
-    U,
-    D,
-    L,
-    R,
+fn parse_rocks(src: Source, line: &str, x_offset: isize) -> Result<Vec<Point>, ParseError> {
+    let mut points: Vec<Point> = vec![];
+    for seg in line.split(" -> ") {
+        let (x, y) = src.require(seg.split_once(','), seg, "expected 'x,y'")?;
+        let x: usize = src.parse(x)?;
+        let y: usize = src.parse(y)?;
+        let point = Point::new(x as isize - x_offset, y as isize);
+
+        // paths are made of horizontal and vertical lines only
+        if let Some(prev) = points.last() {
+            if prev.x != point.x && prev.y != point.y {
+                return Err(src.error(seg, "path must be horizontal or vertical"));
+            }
+        }
+        points.push(point);
+    }
+
+    Ok(points)
 }
-impl From<Dir> for Point {
-    fn from(dir: Dir) -> Self {
-        match dir {
-            Dir::U => Point(0, 1),
-            Dir::D => Point(0, -1),
-            Dir::L => Point(-1, 0),
-            Dir::R => Point(1, 0),
+
+fn parse_input(test_input: &str) -> anyhow::Result<Problem> {
+    let src = Source::new(test_input);
+    let x_offset = 0; // useful for display / testing
+    let lines = test_input.lines().collect::<Vec<_>>();
+
+    let rocks: Result<Vec<_>, _> = lines
+        .iter()
+        .map(|line| parse_rocks(src, line, x_offset))
+        .collect();
+    let rocks = rocks?;
+
+    // determine dimensions and create grid
+    let max_x = rocks.iter().flatten().map(|p| p.x).max();
+    let max_x = max_x.ok_or_else(|| src.end_error("no rock paths"))?;
+    let max_y = rocks
+        .iter()
+        .flatten()
+        .map(|p| p.y)
+        .max()
+        .unwrap_or_default();
+    let mut grid = Grid::new(max_x as usize + 500, max_y as usize + 2, Blank);
+
+    // populate grid with the rocks
+    for rock in rocks {
+        for pair in rock.windows(2) {
+            if let [a, b] = pair {
+                let dir = (*b - *a).signum();
+                let mut cur = *a;
+                while cur != *b {
+                    *grid.get_mut(cur).ok_anyhow()? = Rock;
+                    cur += dir;
+                }
+                *grid.get_mut(cur).ok_anyhow()? = Rock;
+            }
         }
     }
+
+    let sand_origin = Point::new(500 - x_offset, 0);
+    Ok(Problem {
+        grid,
+        sand_origin,
+        max_y,
+    })
 }
 
-#[derive(Debug, Clone)]
-pub struct Grid<T> {
-    values: Vec<T>,
-    width: usize,
-    height: usize,
+fn part1(problem: &mut Problem) -> anyhow::Result<i32> {
+    let mut came_to_rest = 0;
+    while let Some(resting_location) = problem.drop_sand(None) {
+        let entry = problem.grid.get_mut(resting_location).ok_anyhow()?;
+        *entry = Sand;
+        came_to_rest += 1;
+    }
+
+    Ok(came_to_rest)
 }
-impl<T: Clone> Grid<T> {
-    pub fn new(width: usize, height: usize, fill_value: T) -> Self {
-        Grid {
-            width,
-            height,
-            values: vec![fill_value; width * height],
+
+fn part2(problem: &mut Problem) -> anyhow::Result<i32> {
+    let floor = problem.max_y + 2;
+    let mut came_to_rest = 0;
+    loop {
+        let resting_location = problem.drop_sand(Some(floor)).ok_anyhow()?;
+        *problem.grid.get_mut(resting_location).ok_anyhow()? = Sand;
+        came_to_rest += 1;
+
+        // stop when we block the source
+        if resting_location == problem.sand_origin {
+            break;
         }
     }
+    Ok(came_to_rest)
 }
-impl<T> Grid<T> {
-    pub fn get(&self, point: &Point) -> Option<&T> {
-        if self.contains(point) {
-            let ix = point.0 + point.1 * self.width as isize;
-            self.values.get(ix as usize)
-        } else {
-            None
-        }
+
+pub struct Day14;
+impl Solution for Day14 {
+    type Input = Problem;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
     }
 
-    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
-        if self.contains(point) {
-            let ix = point.0 + point.1 * self.width as isize;
-            self.values.get_mut(ix as usize)
-        } else {
-            None
-        }
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(&mut input.clone())
     }
 
-    pub fn contains(&self, point: &Point) -> bool {
-        (0..self.width as isize).contains(&point.0) && (0..self.height as isize).contains(&point.1)
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(&mut input.clone())
     }
 }
-impl<T: Display> Display for Grid<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let element = self.get(&Point(x as isize, y as isize)).unwrap();
-                write!(f, "{}", element)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+
+pub const DAY: Day = Day::new::<Day14>(14, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        498,4 -> 498,6 -> 496,6
+        503,4 -> 502,4 -> 502,9 -> 494,9
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        parse_input(TEST_INPUT).unwrap();
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum GridSquare {
-    Blank,
-    Rock,
-    Sand,
-}
-impl Display for GridSquare {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                GridSquare::Blank => ".",
-                GridSquare::Rock => "#",
-                GridSquare::Sand => "o",
-            }
-        )
+    #[test]
+    fn part1_correct() {
+        let mut problem = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&mut problem).unwrap();
+        // println!("{}", problem.grid);
+        assert_eq!(res, 24);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let mut problem = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&mut problem).unwrap();
+        // println!("{}", problem.grid);
+        assert_eq!(res, 93);
+    }
+
+    #[test]
+    fn diagonal_path_located() {
+        let err = parse_input(&TEST_INPUT.replace("502,9", "501,9")).unwrap_err();
+        let err = err.downcast_ref::<common::parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 19, "501,9"));
     }
 }
@@ -0,0 +1,281 @@
+use anyhow::{anyhow, bail};
+use regex::Regex;
+
+use common::{geometry, interval::IntervalSet, parse::Source, AnyResult, Day, Solution};
+
+pub type Point = geometry::Point<i64>;
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    sensor: Point,
+    beacon: Point,
+    distance: i64,
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<Measurement>> {
+    let re = Regex::new(
+        r#"^Sensor at x=([+-]?\d+), y=([+-]?\d+): closest beacon is at x=([+-]?\d+), y=([+-]?\d+)$"#,
+    )?;
+    let src = Source::new(input);
+
+    input
+        .lines()
+        .map(|l| {
+            let cap = src.require(
+                re.captures(l),
+                l,
+                "expected 'Sensor at x=X, y=Y: closest beacon is at x=X, y=Y'",
+            )?;
+            let field = |i| src.parse(cap.get(i).unwrap().as_str());
+
+            let sensor = Point::new(field(1)?, field(2)?);
+            let beacon = Point::new(field(3)?, field(4)?);
+
+            let distance = beacon.manhattan_distance(sensor);
+
+            Ok(Measurement {
+                sensor,
+                beacon,
+                distance,
+            })
+        })
+        .collect()
+}
+
+fn line_coverage(measurements: &[Measurement], reference_row: i64) -> IntervalSet<i64> {
+    measurements
+        .iter()
+        .filter_map(|m| {
+            let x = m.sensor.x;
+            let dist_y = (m.sensor.y - reference_row).abs();
+            let dx = m.distance - dist_y;
+            (dx >= 0).then(|| x - dx..=x + dx)
+        })
+        .collect()
+}
+
+fn part1(measurements: &[Measurement], reference_row: i64) -> AnyResult<usize> {
+    // get coverage for this line
+    let mut line_covered = line_coverage(measurements, reference_row);
+
+    // exclude beacons on this line
+    for m in measurements {
+        if m.beacon.y == reference_row {
+            line_covered.remove(m.beacon.x..=m.beacon.x);
+        }
+    }
+
+    let covered = line_covered
+        .total_len()
+        .ok_or_else(|| anyhow!("too much of row {reference_row} is covered to count"))?;
+    Ok(covered.try_into()?)
+}
+
+/// Turn the board 45 degrees, to u = x + y and v = x - y, where each
+/// sensor's diamond becomes a square: (u range, v range).
+fn rotated_squares(measurements: &[Measurement]) -> Vec<(i64, i64, i64, i64)> {
+    measurements
+        .iter()
+        .map(|m| {
+            let (u, v) = (m.sensor.x + m.sensor.y, m.sensor.x - m.sensor.y);
+            (
+                u - m.distance,
+                u + m.distance,
+                v - m.distance,
+                v + m.distance,
+            )
+        })
+        .collect()
+}
+
+/// Every point with both coordinates in `min_coord..=max_coord` that no
+/// sensor can see, in order of x + y, then x - y.
+///
+/// In rotated coordinates the edges of the sensors' squares are lines of
+/// constant u or v. Between two neighbouring u lines the same sensors cover
+/// the same stretches of v, so the uncovered points there are the gaps in
+/// those stretches, cut down to the search box, which is a diamond turned
+/// this way. Only points whose u and v have the same parity are real.
+///
+/// This sweeps between the boundary lines rather than only trying where
+/// pairs of them cross. Crossings find a lone uncovered point, but can't
+/// list every point of a larger uncovered region. Finding the regions costs
+/// at worst O(n³) for n sensors, whatever the size of the box. Listing the
+/// points then costs one step per point, so an uncovered region the size of
+/// the box takes O(area). The points are produced lazily, so taking only
+/// the first few stays cheap.
+pub fn uncovered_points(
+    measurements: &[Measurement],
+    min_coord: i64,
+    max_coord: i64,
+) -> impl Iterator<Item = Point> {
+    let squares = rotated_squares(measurements);
+    let (box_min, box_max) = (2 * min_coord, 2 * max_coord);
+
+    // where coverage starts and stops along u, inside the box
+    let mut lines: Vec<i64> = squares
+        .iter()
+        .flat_map(|&(u0, u1, _, _)| [u0, u1 + 1])
+        .chain([box_min, box_max + 1])
+        .filter(|u| (box_min..=box_max + 1).contains(u))
+        .collect();
+    lines.sort_unstable();
+    lines.dedup();
+
+    // a gap in v, and the u values for which some of it is in the box
+    let mut regions = vec![];
+    for pair in lines.windows(2) {
+        let (u_start, u_next) = (pair[0], pair[1]);
+        let covered: IntervalSet<i64> = squares
+            .iter()
+            .filter(|&&(u0, u1, _, _)| u0 <= u_start && u_start <= u1)
+            .map(|&(_, _, v0, v1)| v0..=v1)
+            .collect();
+        for gap in covered.gaps(min_coord - max_coord..=max_coord - min_coord) {
+            let (g0, g1) = gap.into_inner();
+            let u_lo = u_start.max(box_min - g1).max(g0 + box_min);
+            let u_hi = (u_next - 1).min(g1 + box_max).min(box_max - g0);
+            if u_lo <= u_hi {
+                regions.push((u_lo..=u_hi, g0..=g1));
+            }
+        }
+    }
+
+    regions.into_iter().flat_map(move |(us, gap)| {
+        us.flat_map(move |u| {
+            // the box's v range for this u
+            let v_lo = (box_min - u).max(u - box_max).max(*gap.start());
+            let v_hi = (box_max - u).min(u - box_min).min(*gap.end());
+            let v_lo = v_lo + (v_lo - u).rem_euclid(2);
+            (v_lo..=v_hi)
+                .step_by(2)
+                .map(move |v| Point::new((u + v) / 2, (u - v) / 2))
+        })
+    })
+}
+
+fn tuning_frequency(point: Point) -> i64 {
+    4000000 * point.x + point.y
+}
+
+fn part2(measurements: &[Measurement], min_coord: i64, max_coord: i64) -> AnyResult<i64> {
+    let points: Vec<_> = uncovered_points(measurements, min_coord, max_coord)
+        .take(2)
+        .collect();
+    match points[..] {
+        [point] => Ok(tuning_frequency(point)),
+        [] => bail!("every point is covered by a sensor"),
+        _ => bail!(
+            "there's more than one uncovered point, including {:?} and {:?}",
+            points[0],
+            points[1]
+        ),
+    }
+}
+
+pub struct Day15;
+impl Solution for Day15 {
+    type Input = Vec<Measurement>;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(input, 2000000)
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input, 0, 4000000)
+    }
+}
+
+pub const DAY: Day = Day::new::<Day15>(15, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16
+        Sensor at x=13, y=2: closest beacon is at x=15, y=3
+        Sensor at x=12, y=14: closest beacon is at x=10, y=16
+        Sensor at x=10, y=20: closest beacon is at x=10, y=16
+        Sensor at x=14, y=17: closest beacon is at x=10, y=16
+        Sensor at x=8, y=7: closest beacon is at x=2, y=10
+        Sensor at x=2, y=0: closest beacon is at x=2, y=10
+        Sensor at x=0, y=11: closest beacon is at x=2, y=10
+        Sensor at x=20, y=14: closest beacon is at x=25, y=17
+        Sensor at x=17, y=20: closest beacon is at x=21, y=22
+        Sensor at x=16, y=7: closest beacon is at x=15, y=3
+        Sensor at x=14, y=3: closest beacon is at x=15, y=3
+        Sensor at x=20, y=1: closest beacon is at x=15, y=3
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        println!("{measurements:?}");
+    }
+
+    #[test]
+    fn part1_alt_correct() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&measurements, 10).unwrap();
+        assert_eq!(res, 26);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&measurements, 0, 20).unwrap();
+        assert_eq!(res, 56000011);
+    }
+
+    #[test]
+    fn line_coverage_correct() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        let coverage = line_coverage(&measurements, 11);
+        assert_eq!(coverage.iter().collect::<Vec<_>>(), [-3..=13, 15..=25]);
+    }
+
+    fn covered(measurements: &[Measurement], point: Point) -> bool {
+        measurements
+            .iter()
+            .any(|m| m.sensor.manhattan_distance(point) <= m.distance)
+    }
+
+    #[test]
+    fn uncovered_points_match_brute_force() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        for (min, max) in [(0, 20), (-5, 30), (10, 14), (13, 15), (-3, 0)] {
+            let mut expected = vec![];
+            for x in min..=max {
+                for y in min..=max {
+                    let point = Point::new(x, y);
+                    if !covered(&measurements, point) {
+                        expected.push(point);
+                    }
+                }
+            }
+            let mut found: Vec<_> = uncovered_points(&measurements, min, max).collect();
+            found.sort();
+            assert_eq!(found, expected, "box {min}..={max}");
+        }
+    }
+
+    #[test]
+    fn part2_needs_exactly_one_point() {
+        let measurements = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            uncovered_points(&measurements, 0, 20).collect::<Vec<_>>(),
+            [Point::new(14, 11)]
+        );
+        assert!(part2(&measurements, -5, 30).is_err());
+        assert!(part2(&measurements, 10, 12).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+pub mod plan;
+
+use anyhow::bail;
+use plan::{Actor, Plan};
+use regex::Regex;
+use std::{collections::HashMap, fmt::Display};
+
+use common::{
+    parse::{ParseError, Source},
+    search::bfs,
+    AnyResult, Day, Solution,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Code([char; 2]);
+impl Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.0[0], self.0[1])
+    }
+}
+impl std::fmt::Debug for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self, f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Valve {
+    code: Code,
+    rate: i32,
+    connects_to: Vec<Code>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    valves: HashMap<Code, Valve>,
+    start: Code,
+}
+
+fn parse_code(src: Source, code: &str) -> Result<Code, ParseError> {
+    match code.chars().collect::<Vec<_>>()[..] {
+        [a, b] => Ok(Code([a, b])),
+        _ => Err(src.error(code, "expected a two letter valve code")),
+    }
+}
+
+fn parse_input(input: &str) -> AnyResult<Problem> {
+    let re = Regex::new(
+        r#"^Valve ([A-Z]+) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z]+(?:, [A-Z]+)*)$"#,
+    )?;
+    let src = Source::new(input);
+    // each tunnel's destination, as written, to check that it exists
+    let mut tunnels = vec![];
+
+    let valves: AnyResult<HashMap<Code, Valve>> = input
+        .lines()
+        .map(|l| {
+            let cap = src.require(re.captures(l), l, "expected a valve description")?;
+
+            let code = parse_code(src, cap.get(1).unwrap().as_str())?;
+            let rate = src.parse(cap.get(2).unwrap().as_str())?;
+
+            let names: Vec<&str> = cap.get(3).unwrap().as_str().split(", ").collect();
+            let connects_to: Result<Vec<_>, _> = names.iter().map(|c| parse_code(src, c)).collect();
+            let connects_to = connects_to?;
+            tunnels.extend(names.into_iter().zip(connects_to.iter().copied()));
+
+            Ok((
+                code,
+                Valve {
+                    code,
+                    rate,
+                    connects_to,
+                },
+            ))
+        })
+        .collect();
+
+    let valves = valves?;
+    if let Some((name, code)) = tunnels.iter().find(|(_, code)| !valves.contains_key(code)) {
+        return Err(src.error(name, format!("there's no valve {code}")).into());
+    }
+    let start = Code(['A', 'A']);
+    if !valves.contains_key(&start) {
+        return Err(src.end_error("missing the starting valve AA").into());
+    }
+
+    Ok(Problem { valves, start })
+}
+
+fn check_all_bidirectional(problem: &Problem) -> AnyResult<()> {
+    let valves = &problem.valves;
+    for (code, valve) in valves {
+        for connected in valve.connects_to.iter() {
+            // parsing checked that every tunnel leads to a valve
+            let other = &valves[connected];
+            if !other.connects_to.contains(code) {
+                bail!("Valve {connected} does not connect back to {code}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Most valves have no flow, so are only worth walking through. The network
+/// keeps just the valves worth opening, with the walking time between each
+/// pair of them; a route is then a sequence of valves to open.
+#[derive(Debug, Clone)]
+pub struct Network {
+    codes: Vec<Code>,
+    start: Code,
+    rates: Vec<i32>,
+    /// `distances[i][j]` is the minutes taken to walk from valve `i` to `j`.
+    distances: Vec<Vec<i32>>,
+    from_start: Vec<i32>,
+}
+
+// subsets of valves are indexed by bitmask, so keep the table a sensible size
+const MAX_VALVES_WITH_FLOW: usize = 20;
+
+// far enough that the valve is never worth walking to
+const UNREACHABLE: i32 = i32::MAX / 2;
+
+impl Network {
+    fn new(problem: &Problem) -> AnyResult<Self> {
+        let mut flowing: Vec<&Valve> = problem.valves.values().filter(|v| v.rate > 0).collect();
+        flowing.sort_by_key(|v| v.code);
+        if flowing.len() > MAX_VALVES_WITH_FLOW {
+            bail!(
+                "{} valves have a flow rate; at most {MAX_VALVES_WITH_FLOW} are supported",
+                flowing.len()
+            );
+        }
+        let codes: Vec<Code> = flowing.iter().map(|v| v.code).collect();
+
+        // each tunnel takes a minute, so a breadth-first search gives the
+        // walking time to everywhere
+        let distances_from = |from: Code| -> Vec<i32> {
+            let reached = bfs(
+                [from],
+                |code| problem.valves[code].connects_to.iter().copied(),
+                |_| false,
+            );
+            codes
+                .iter()
+                .map(|code| reached.cost(code).map_or(UNREACHABLE, |d| d as i32))
+                .collect()
+        };
+
+        Ok(Network {
+            codes: codes.clone(),
+            start: problem.start,
+            rates: flowing.iter().map(|v| v.rate).collect(),
+            distances: codes.iter().map(|&c| distances_from(c)).collect(),
+            from_start: distances_from(problem.start),
+        })
+    }
+
+    fn travel_time(&self, from: Option<usize>, to: usize) -> i32 {
+        match from {
+            Some(from) => self.distances[from][to],
+            None => self.from_start[to],
+        }
+    }
+
+    /// The most pressure one person can release in `time` minutes by
+    /// opening exactly the valves in each subset, indexed by bitmask.
+    /// Subsets that can't all be opened in time are left at 0.
+    fn best_per_subset(&self, time: i32) -> Vec<i32> {
+        let mut best = vec![0; 1 << self.rates.len()];
+        let all = best.len() - 1;
+        self.visit(all, time, 0, 0, &mut vec![], &mut |opened, released, _| {
+            best[opened] = best[opened].max(released);
+        });
+        best
+    }
+
+    /// The order to open valves from `subset` in to release the most
+    /// pressure in `time` minutes, which needn't open all of them.
+    fn best_route(&self, subset: usize, time: i32) -> Vec<usize> {
+        let mut best = (0, vec![]);
+        self.visit(
+            subset,
+            time,
+            0,
+            0,
+            &mut vec![],
+            &mut |_, released, route| {
+                if released > best.0 {
+                    best = (released, route.to_vec());
+                }
+            },
+        );
+        best.1
+    }
+
+    // try every order of opening valves from `allowed` that fits in the
+    // time left, calling `found` with each route
+    fn visit(
+        &self,
+        allowed: usize,
+        time_left: i32,
+        opened: usize,
+        released: i32,
+        route: &mut Vec<usize>,
+        found: &mut impl FnMut(usize, i32, &[usize]),
+    ) {
+        found(opened, released, route);
+        let at = route.last().copied();
+        for next in 0..self.rates.len() {
+            if opened & (1 << next) != 0 || allowed & (1 << next) == 0 {
+                continue;
+            }
+            // walk there, then a minute to open the valve; it releases
+            // pressure for the rest of the time
+            let remaining = time_left - self.travel_time(at, next) - 1;
+            if remaining <= 0 {
+                continue;
+            }
+            let released = released + self.rates[next] * remaining;
+            route.push(next);
+            let opened = opened | 1 << next;
+            self.visit(allowed, remaining, opened, released, route, found);
+            route.pop();
+        }
+    }
+}
+
+/// Widen each entry of `best` to the best over every subset of its mask,
+/// rather than for that exact mask.
+fn best_within_subsets(mut best: Vec<i32>) -> Vec<i32> {
+    let bits = best.len().trailing_zeros();
+    for bit in 0..bits {
+        for mask in 0..best.len() {
+            if mask & (1 << bit) != 0 {
+                best[mask] = best[mask].max(best[mask ^ (1 << bit)]);
+            }
+        }
+    }
+    best
+}
+
+/// The best plan for opening valves alone in 30 minutes.
+pub fn plan_part1(network: &Network) -> Plan {
+    let all = (1 << network.rates.len()) - 1;
+    let route = network.best_route(all, TIME_PART1);
+    network.plan(TIME_PART1, &[(Actor::You, route)])
+}
+
+/// The best plan for opening valves with the elephant in 26 minutes.
+// the elephant and I each open a disjoint set of valves, so pair up the best
+// for each set with the best the other can do with what's left
+pub fn plan_part2(network: &Network) -> Plan {
+    let best = network.best_per_subset(TIME_PART2);
+    let all = best.len() - 1;
+    let within = best_within_subsets(best.clone());
+    let mine = (0..best.len())
+        .max_by_key(|&mine| best[mine] + within[all ^ mine])
+        .unwrap_or_default();
+    let orders = [
+        (Actor::You, network.best_route(mine, TIME_PART2)),
+        (Actor::Elephant, network.best_route(all ^ mine, TIME_PART2)),
+    ];
+    network.plan(TIME_PART2, &orders)
+}
+
+fn part1(network: &Network) -> i32 {
+    plan_part1(network).released()
+}
+
+fn part2(network: &Network) -> i32 {
+    plan_part2(network).released()
+}
+
+const TIME_PART1: i32 = 30;
+const TIME_PART2: i32 = 26;
+
+pub struct Day16;
+impl Solution for Day16 {
+    type Input = Network;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        let problem = parse_input(input)?;
+        check_all_bidirectional(&problem)?;
+        Network::new(&problem)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day16>(16, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    pub(crate) const TEST_INPUT: &str = indoc! {"
+        Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+        Valve BB has flow rate=13; tunnels lead to valves CC, AA
+        Valve CC has flow rate=2; tunnels lead to valves DD, BB
+        Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+        Valve EE has flow rate=3; tunnels lead to valves FF, DD
+        Valve FF has flow rate=0; tunnels lead to valves EE, GG
+        Valve GG has flow rate=0; tunnels lead to valves FF, HH
+        Valve HH has flow rate=22; tunnel leads to valve GG
+        Valve II has flow rate=0; tunnels lead to valves AA, JJ
+        Valve JJ has flow rate=21; tunnel leads to valve II
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        check_all_bidirectional(&problem).unwrap();
+        println!("{problem:?}");
+    }
+
+    #[test]
+    fn missing_valves_located() {
+        let input = TEST_INPUT.replace("valves FF, DD", "valves FF, XX");
+        let err = parse_input(&input).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 54, "XX"));
+        assert!(err.to_string().contains("there's no valve XX"));
+    }
+
+    pub(crate) fn test_network() -> Network {
+        Network::new(&parse_input(TEST_INPUT).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn network_distances() {
+        let network = test_network();
+        // valves BB, CC, DD, EE, HH and JJ have a flow rate
+        assert_eq!(network.rates, [13, 2, 20, 3, 22, 21]);
+        assert_eq!(network.from_start, [1, 2, 1, 2, 5, 2]);
+        // JJ to HH goes back through AA, DD, EE, FF and GG
+        assert_eq!(network.distances[5][4], 7);
+    }
+
+    #[test]
+    fn part1_correct() {
+        let res = part1(&test_network());
+        assert_eq!(res, 1651);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let res = part2(&test_network());
+        assert_eq!(res, 1707);
+    }
+}
@@ -0,0 +1,289 @@
+//! Plans of which valves to open when, to show how a best answer is
+//! reached, and a simulator to check a plan against the original tunnels.
+
+use std::{collections::HashMap, fmt::Display};
+
+use anyhow::{anyhow, bail, ensure};
+use common::{search::bfs, AnyResult, Solution};
+
+use crate::{parse_input, plan_part1, plan_part2, Code, Day16, Network, Problem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actor {
+    You,
+    Elephant,
+}
+impl Actor {
+    /// A sentence about the actor doing `verb`, followed by `rest`.
+    fn does(&self, verb: &str, rest: &str) -> String {
+        match self {
+            Actor::You => format!("You {verb} {rest}"),
+            Actor::Elephant => format!("The elephant {verb}s {rest}"),
+        }
+    }
+}
+
+/// Walking to a valve and opening it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub valve: Code,
+    /// The minute the walk there starts in.
+    pub depart: i32,
+    /// The minute the valve is opened in; it releases pressure from the
+    /// next minute on.
+    pub open: i32,
+    pub rate: i32,
+    pub released: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub actor: Actor,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub start: Code,
+    pub time: i32,
+    pub routes: Vec<Route>,
+}
+
+impl Plan {
+    /// The pressure released by all the valves the plan opens.
+    pub fn released(&self) -> i32 {
+        self.routes
+            .iter()
+            .flat_map(|r| &r.steps)
+            .map(|s| s.released)
+            .sum()
+    }
+}
+
+impl Network {
+    /// The plan for each actor to open valves in the given orders, each
+    /// walking straight to the next valve.
+    pub fn plan(&self, time: i32, orders: &[(Actor, Vec<usize>)]) -> Plan {
+        let routes = orders
+            .iter()
+            .map(|(actor, order)| {
+                let mut at = None;
+                let mut minute = 0;
+                let steps = order
+                    .iter()
+                    .map(|&valve| {
+                        let depart = minute + 1;
+                        minute += self.travel_time(at, valve) + 1;
+                        at = Some(valve);
+                        Step {
+                            valve: self.codes[valve],
+                            depart,
+                            open: minute,
+                            rate: self.rates[valve],
+                            released: self.rates[valve] * (time - minute),
+                        }
+                    })
+                    .collect();
+                Route {
+                    actor: *actor,
+                    steps,
+                }
+            })
+            .collect();
+        Plan {
+            start: self.start,
+            time,
+            routes,
+        }
+    }
+}
+
+/// Everything that happens, minute by minute.
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut events: Vec<(i32, usize, String)> = vec![];
+        for (order, route) in self.routes.iter().enumerate() {
+            for step in &route.steps {
+                let walk = step.open - step.depart;
+                let minutes = if walk == 1 { "minute" } else { "minutes" };
+                let walk = route.actor.does(
+                    "set",
+                    &format!("off for valve {}, {walk} {minutes} away.", step.valve),
+                );
+                let open = route.actor.does(
+                    "open",
+                    &format!(
+                        "valve {}, releasing {} pressure for {} minutes: {} in all.",
+                        step.valve,
+                        step.rate,
+                        self.time - step.open,
+                        step.released
+                    ),
+                );
+                events.push((step.depart, order, walk));
+                events.push((step.open, order, open));
+            }
+        }
+        events.sort();
+
+        let mut minute = 0;
+        for (at, _, event) in events {
+            if at != minute {
+                if minute != 0 {
+                    writeln!(f)?;
+                }
+                writeln!(f, "== Minute {at} ==")?;
+                minute = at;
+            }
+            writeln!(f, "{event}")?;
+        }
+        if minute != 0 {
+            writeln!(f)?;
+        }
+        write!(f, "Total pressure released: {}", self.released())
+    }
+}
+
+/// Replay `plan` on the tunnels of `problem` minute by minute, checking
+/// that every walk can be made in the time given and that no valve is
+/// opened twice. Returns the pressure released.
+pub fn simulate(problem: &Problem, plan: &Plan) -> AnyResult<i32> {
+    let mut opened_in: HashMap<Code, i32> = HashMap::new();
+    for route in &plan.routes {
+        let mut at = problem.start;
+        let mut busy_until = 0;
+        for step in &route.steps {
+            let walk = bfs(
+                [at],
+                |code| problem.valves[code].connects_to.iter().copied(),
+                |code| *code == step.valve,
+            )
+            .cost(&step.valve)
+            .ok_or_else(|| anyhow!("there's no way from {at} to {}", step.valve))?;
+
+            if step.depart <= busy_until {
+                bail!(
+                    "{:?} sets off for {} while still busy",
+                    route.actor,
+                    step.valve
+                );
+            }
+            // walking takes the minutes from departure, and opening one more
+            if step.open < step.depart + walk as i32 {
+                bail!(
+                    "{:?} can't reach {} by minute {}",
+                    route.actor,
+                    step.valve,
+                    step.open
+                );
+            }
+            if step.open > plan.time {
+                bail!("{:?} opens {} after time is up", route.actor, step.valve);
+            }
+            if opened_in.insert(step.valve, step.open).is_some() {
+                bail!("{} is opened twice", step.valve);
+            }
+            at = step.valve;
+            busy_until = step.open;
+        }
+    }
+
+    let mut released = 0;
+    for minute in 1..=plan.time {
+        released += opened_in
+            .iter()
+            .filter(|(_, &open)| open < minute)
+            .map(|(code, _)| problem.valves[code].rate)
+            .sum::<i32>();
+    }
+    Ok(released)
+}
+
+/// The best plans for both parts of the puzzle in `input`, each replayed on
+/// the tunnels to check it.
+pub fn best_plans(input: &str) -> AnyResult<[Plan; 2]> {
+    let problem = parse_input(input)?;
+    let network = Day16::parse(input)?;
+    let plans = [plan_part1(&network), plan_part2(&network)];
+    for plan in &plans {
+        let released = simulate(&problem, plan)?;
+        ensure!(
+            released == plan.released(),
+            "the plan releases {released} pressure, not {}",
+            plan.released()
+        );
+    }
+    Ok(plans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_network, TEST_INPUT};
+    use indoc::indoc;
+
+    #[test]
+    fn plans_replay() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let network = test_network();
+        for plan in [plan_part1(&network), plan_part2(&network)] {
+            assert_eq!(simulate(&problem, &plan).unwrap(), plan.released());
+        }
+    }
+
+    #[test]
+    fn best_plans_for_both_parts() {
+        let [part1, part2] = best_plans(TEST_INPUT).unwrap();
+        assert_eq!(part1.released(), 1651);
+        assert_eq!(part2.released(), 1707);
+        let actors: Vec<_> = part2.routes.iter().map(|r| r.actor).collect();
+        assert_eq!(actors, [Actor::You, Actor::Elephant]);
+        assert!(part2.to_string().contains("The elephant opens valve"));
+    }
+
+    #[test]
+    fn part1_route_matches_puzzle() {
+        let plan = plan_part1(&test_network());
+        let valves: Vec<_> = plan.routes[0]
+            .steps
+            .iter()
+            .map(|s| s.valve.to_string())
+            .collect();
+        assert_eq!(valves, ["DD", "BB", "JJ", "HH", "EE", "CC"]);
+        assert_eq!(plan.released(), 1651);
+    }
+
+    #[test]
+    fn prints_timeline() {
+        let network = test_network();
+        let plan = network.plan(30, &[(Actor::You, vec![2]), (Actor::Elephant, vec![0])]);
+        let expected = indoc! {"
+            == Minute 1 ==
+            You set off for valve DD, 1 minute away.
+            The elephant sets off for valve BB, 1 minute away.
+
+            == Minute 2 ==
+            You open valve DD, releasing 20 pressure for 28 minutes: 560 in all.
+            The elephant opens valve BB, releasing 13 pressure for 28 minutes: 364 in all.
+
+            Total pressure released: 924"};
+        assert_eq!(plan.to_string(), expected);
+    }
+
+    #[test]
+    fn bad_plans_rejected() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let network = test_network();
+
+        let twice = network.plan(30, &[(Actor::You, vec![2]), (Actor::Elephant, vec![2])]);
+        assert!(simulate(&problem, &twice).is_err());
+
+        // HH is five minutes away, not one
+        let mut too_fast = network.plan(30, &[(Actor::You, vec![4])]);
+        too_fast.routes[0].steps[0].open = 2;
+        assert!(simulate(&problem, &too_fast).is_err());
+
+        let too_late = network.plan(3, &[(Actor::You, vec![4])]);
+        assert!(simulate(&problem, &too_late).is_err());
+    }
+}
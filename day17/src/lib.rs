@@ -0,0 +1,672 @@
+use anyhow::bail;
+use common::{cycle::History, *};
+use indoc::indoc;
+use itertools::Itertools;
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+    path::Path,
+};
+
+/// The widest chamber that fits in the bits of a [`Row`].
+pub const MAX_WIDTH: usize = Row::BITS as usize;
+
+/// One row of the chamber or of a rock, with a bit set for each column
+/// that's filled; the leftmost column is the lowest bit.
+type Row = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rock {
+    /// The rows from the bottom up, against the left edge.
+    rows: Vec<Row>,
+    width: usize,
+}
+impl Rock {
+    /// A rock from its rows drawn top down, as `true` for each filled cell.
+    /// The bottom row can't be empty, since a rock resting on nothing would
+    /// sink into rows pruned from the bottom of the tower.
+    pub fn new(drawing: &[Vec<bool>]) -> AnyResult<Self> {
+        let width = drawing.first().map_or(0, |r| r.len());
+        if width > MAX_WIDTH {
+            bail!("rocks can be at most {MAX_WIDTH} wide, not {width}");
+        }
+        if drawing.iter().any(|r| r.len() != width) {
+            bail!("every row of a rock must be the same width");
+        }
+        let rows = drawing
+            .iter()
+            .rev()
+            .map(|r| {
+                r.iter()
+                    .enumerate()
+                    .filter(|(_, &filled)| filled)
+                    .fold(0, |row, (c, _)| row | 1 << c)
+            })
+            .collect::<Vec<Row>>();
+        if rows.first() == Some(&0) {
+            bail!("the bottom row of a rock can't be empty");
+        }
+        Ok(Rock { rows, width })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+impl Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows.iter().rev() {
+            for c in 0..self.width {
+                f.write_char(if row & 1 << c != 0 { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The puzzle's five rocks, in the format read by [`parse_rocks`].
+pub const STANDARD_ROCKS: &str = include_str!("../rocks.txt");
+
+/// The shape of the chamber and the rocks that fall into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub width: usize,
+    /// The rocks, in the order they fall.
+    pub rocks: Vec<Rock>,
+    /// Columns between the left wall and the left edge of a new rock.
+    pub spawn_left: usize,
+    /// Empty rows between the top of the tower and the bottom of a new rock.
+    pub spawn_gap: usize,
+}
+impl Config {
+    pub fn new(
+        width: usize,
+        rocks: Vec<Rock>,
+        spawn_left: usize,
+        spawn_gap: usize,
+    ) -> AnyResult<Self> {
+        if rocks.is_empty() {
+            bail!("there must be at least one rock");
+        }
+        if width > MAX_WIDTH {
+            bail!("the chamber can be at most {MAX_WIDTH} wide, not {width}");
+        }
+        for (i, rock) in rocks.iter().enumerate() {
+            if spawn_left + rock.width > width {
+                bail!(
+                    "rock {} is {} wide, and doesn't fit {spawn_left} from the left of a chamber {width} wide",
+                    i + 1,
+                    rock.width
+                );
+            }
+        }
+        Ok(Config {
+            width,
+            rocks,
+            spawn_left,
+            spawn_gap,
+        })
+    }
+
+    /// The chamber and rocks from the puzzle.
+    pub fn standard() -> Self {
+        let rocks = parse_rocks(STANDARD_ROCKS).expect("standard rocks are valid");
+        Config::new(7, rocks, 2, 3).expect("standard rocks fit")
+    }
+}
+impl Default for Config {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Read rock shapes drawn with `#` and `.`, separated by blank lines.
+pub fn parse_rocks(input: &str) -> AnyResult<Vec<Rock>> {
+    let src = parse::Source::new(input);
+    let mut rocks = vec![];
+    let mut lines = input.lines().peekable();
+    while lines.peek().is_some() {
+        let block: Vec<&str> = lines
+            .by_ref()
+            .take_while(|l| !l.trim().is_empty())
+            .collect();
+        let Some(first) = block.first() else {
+            continue;
+        };
+
+        let width = first.chars().count();
+        if width > MAX_WIDTH {
+            return Err(src
+                .error(first, format!("rocks can be at most {MAX_WIDTH} wide"))
+                .into());
+        }
+        let mut drawing = vec![];
+        for line in &block {
+            if line.chars().count() != width {
+                return Err(src
+                    .error(line, format!("expected a row {width} wide"))
+                    .into());
+            }
+            let mut row = vec![];
+            for (i, ch) in line.char_indices() {
+                row.push(match ch {
+                    '#' => true,
+                    '.' => false,
+                    _ => {
+                        return Err(src
+                            .error(&line[i..i + ch.len_utf8()], "expected '#' or '.'")
+                            .into())
+                    }
+                });
+            }
+            drawing.push(row);
+        }
+        if !drawing.iter().flatten().any(|&filled| filled) {
+            return Err(src.error(first, "rock has no '#'").into());
+        }
+        let bottom = block.last().expect("block isn't empty");
+        if !bottom.contains('#') {
+            return Err(src
+                .error(bottom, "the bottom row of a rock can't be empty")
+                .into());
+        }
+        rocks.push(Rock::new(&drawing)?);
+    }
+    if rocks.is_empty() {
+        return Err(src.end_error("no rocks").into());
+    }
+    Ok(rocks)
+}
+
+/// Read rock shapes from a file; see [`parse_rocks`].
+pub fn load_rocks(path: impl AsRef<Path>) -> AnyResult<Vec<Rock>> {
+    parse_rocks(&std::fs::read_to_string(path)?)
+}
+
+pub const TEST_INPUT: &str = indoc! {">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Jet {
+    L,
+    R,
+}
+
+pub type JetPattern = Vec<JetIndex>;
+pub type JetIndex = (usize, Jet);
+
+fn parse_input(input: &str) -> AnyResult<JetPattern> {
+    let src = parse::Source::new(input);
+    let pattern = input.trim_end_matches('\n');
+    let jets = pattern.char_indices().map(|(i, c)| match c {
+        '<' => Ok((i, Jet::L)),
+        '>' => Ok((i, Jet::R)),
+        _ => Err(src.error(&pattern[i..i + c.len_utf8()], "expected a jet '<' or '>'")),
+    });
+    Ok(jets.collect::<Result<_, _>>()?)
+}
+
+/// A rock on its way down: the row of its bottom, and the column of its
+/// left edge.
+type Position = (usize, usize);
+
+struct Problem {
+    /// The rows of the tower from the bottom up, with no empty rows on top;
+    /// rows no rock can reach any more are dropped from the bottom.
+    rows: Vec<Row>,
+    /// How many rows have been dropped.
+    pruned: usize,
+    /// Rows kept after the last pruning, to decide when to next prune.
+    kept: usize,
+    width: usize,
+    /// How many jets have pushed rocks so far.
+    jets_used: usize,
+    spawn_left: usize,
+    spawn_gap: usize,
+    /// The furthest below the top of the tower any rock has come to rest.
+    deepest_landing: usize,
+}
+impl Problem {
+    fn new(config: &Config) -> Self {
+        Problem {
+            rows: vec![],
+            pruned: 0,
+            kept: 0,
+            width: config.width,
+            jets_used: 0,
+            spawn_left: config.spawn_left,
+            spawn_gap: config.spawn_gap,
+            deepest_landing: 0,
+        }
+    }
+
+    fn full_row(&self) -> Row {
+        Row::MAX >> (MAX_WIDTH - self.width)
+    }
+
+    fn initial_position(&self) -> Position {
+        (self.rows.len() + self.spawn_gap, self.spawn_left)
+    }
+
+    /// Whether `rock` fits at `pos` without hitting the walls or the tower.
+    fn fits(&self, rock: &Rock, (bottom, left): Position) -> bool {
+        left + rock.width <= self.width
+            && rock
+                .rows
+                .iter()
+                .zip(self.rows.iter().skip(bottom))
+                .all(|(rock_row, row)| rock_row << left & row == 0)
+    }
+
+    fn try_move(
+        &self,
+        rock: &Rock,
+        (bottom, left): Position,
+        down: bool,
+        cols: isize,
+    ) -> Option<Position> {
+        let bottom = if down { bottom.checked_sub(1)? } else { bottom };
+        let left = left.checked_add_signed(cols)?;
+        self.fits(rock, (bottom, left)).then_some((bottom, left))
+    }
+
+    fn drop_rock(mut self, rock: &Rock, jet_pattern: &mut impl Iterator<Item = JetIndex>) -> Self {
+        // find lowest location to place rock without a conflict
+        let mut current_loc = self.initial_position();
+        let final_loc = loop {
+            // respond to jet on current row
+            let (_, jet) = jet_pattern.next().unwrap();
+            self.jets_used += 1;
+            let col_delta = match jet {
+                Jet::L => -1,
+                Jet::R => 1,
+            };
+
+            if let Some(loc) = self.try_move(rock, current_loc, false, col_delta) {
+                current_loc = loc;
+            }
+
+            // move down 1
+            if let Some(loc) = self.try_move(rock, current_loc, true, 0) {
+                current_loc = loc;
+            } else {
+                // stop - no further move possible
+                break current_loc;
+            }
+        };
+
+        // place rock
+        let (bottom, left) = final_loc;
+        let top = bottom + rock.height();
+        if top > self.rows.len() {
+            self.rows.resize(top, 0);
+        }
+        for (row, rock_row) in self.rows[bottom..top].iter_mut().zip(&rock.rows) {
+            *row |= rock_row << left;
+        }
+        while self.rows.last() == Some(&0) {
+            self.rows.pop();
+        }
+        self.deepest_landing = self
+            .deepest_landing
+            .max(self.rows.len().saturating_sub(bottom));
+
+        // pruning takes as long as the rows it looks at, so wait until there
+        // are plenty more of them than last time
+        if self.rows.len() > 2 * self.kept + 64 {
+            self.prune();
+        }
+
+        self
+    }
+
+    /// Drop the rows below the lowest one any empty cell above the tower
+    /// can be reached in, moving left, right and down as rocks do.
+    fn prune(&mut self) {
+        let full = self.full_row();
+        let mut reached = full;
+        let mut floor = 0;
+        for (r, row) in self.rows.iter().enumerate().rev() {
+            let empty = !row & full;
+            // spread sideways through the empty cells below those reached
+            let mut next = reached & empty;
+            loop {
+                let spread = (next | next << 1 | next >> 1) & empty;
+                if spread == next {
+                    break;
+                }
+                next = spread;
+            }
+            reached = next;
+            if reached == 0 {
+                floor = r + 1;
+                break;
+            }
+        }
+        self.rows.drain(..floor);
+        self.pruned += floor;
+        self.kept = self.rows.len();
+    }
+
+    fn tower_height(&self) -> usize {
+        self.pruned + self.rows.len()
+    }
+
+    /// Every cell that some rock could still be pushed into, as (rows below
+    /// the top of the tower, column). Rocks can't get anywhere else, so
+    /// this is all that matters about the tower for what happens next.
+    ///
+    /// A shaft the jets never happen to push anything down would keep this
+    /// growing forever, though, so it goes only one row deeper than any rock
+    /// has landed so far. Two towers with the same surface and deepest
+    /// landing carry on the same way until a rock lands deeper than that,
+    /// which changes the deepest landing too.
+    fn surface(&self, rocks: &[Rock]) -> Vec<(isize, usize)> {
+        let top = self.rows.len() as isize;
+        let mut cells = HashSet::new();
+        for rock in rocks {
+            let initial = self.initial_position();
+            let mut seen = HashSet::from([initial]);
+            let mut stack = vec![initial];
+            while let Some((bottom, left)) = stack.pop() {
+                for (r, rock_row) in rock.rows.iter().enumerate() {
+                    for c in (0..rock.width).filter(|c| rock_row & 1 << c != 0) {
+                        cells.insert((top - (bottom + r) as isize, left + c));
+                    }
+                }
+                for (down, cols) in [(true, 0), (false, -1), (false, 1)] {
+                    if let Some(next) = self.try_move(rock, (bottom, left), down, cols) {
+                        let depth = top - next.0 as isize;
+                        if depth <= self.deepest_landing as isize + 1 && seen.insert(next) {
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        cells.into_iter().sorted().collect()
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows.iter().rev() {
+            f.write_char('|')?;
+            for c in 0..self.width {
+                f.write_char(if row & 1 << c != 0 { '#' } else { '.' })?;
+            }
+            writeln!(f, "|")?;
+        }
+        let floor = if self.pruned == 0 { '-' } else { '~' };
+        writeln!(f, "+{}+", floor.to_string().repeat(self.width))
+    }
+}
+
+#[allow(dead_code)]
+fn demo() {
+    let config = Config::standard();
+    let jet_pattern = parse_input(TEST_INPUT).unwrap();
+    let mut problem = Problem::new(&config);
+    let mut jets_iter = jet_pattern.iter().cycle().copied();
+    for rock in config.rocks.iter().cycle().take(10) {
+        println!("---------------------------");
+        println!("{rock}");
+        problem = problem.drop_rock(rock, &mut jets_iter);
+        println!("{problem}");
+    }
+}
+
+/// The height of the tower after `rocks` rocks have fallen.
+pub fn tower_height(config: &Config, jet_pattern: &[JetIndex], rocks: usize) -> usize {
+    let mut jets_iter = jet_pattern.iter().cycle().copied();
+    let mut problem = Problem::new(config);
+    for rock in config.rocks.iter().cycle().take(rocks) {
+        problem = problem.drop_rock(rock, &mut jets_iter);
+    }
+    problem.tower_height()
+}
+
+const PART1_ROCKS: usize = 2022;
+const PART2_ROCKS: usize = 1000000000000;
+
+/// Part 1, for any chamber and rocks.
+pub fn part1_with(config: &Config, jet_pattern: &[JetIndex]) -> usize {
+    tower_height(config, jet_pattern, PART1_ROCKS)
+}
+
+fn part1(jet_pattern: &[JetIndex]) -> usize {
+    part1_with(&Config::standard(), jet_pattern)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    rock_mod: usize,
+    jet_mod: usize,
+    /// How deep [`Problem::surface`] looks.
+    deepest_landing: usize,
+    surface: Vec<(isize, usize)>,
+}
+
+/// The height of the tower after `target` rocks have fallen, found by
+/// spotting when the falling rocks start repeating themselves.
+pub fn tower_height_by_cycle(
+    config: &Config,
+    jet_pattern: &[JetIndex],
+    target: usize,
+) -> AnyResult<usize> {
+    let mut jets_iter = jet_pattern.iter().cycle().copied();
+    let mut problem = Problem::new(config);
+    let mut history = History::new();
+
+    for (dropped, (rock_mod, rock)) in config.rocks.iter().enumerate().cycle().enumerate() {
+        let state = State {
+            rock_mod,
+            jet_mod: problem.jets_used % jet_pattern.len(),
+            deepest_landing: problem.deepest_landing,
+            surface: problem.surface(&config.rocks),
+        };
+        if let Some(cycle) = history.push(state, problem.tower_height()) {
+            // the pattern repeats ad-infinitum, so we can skip all the
+            // intermediate steps and work out the final height
+            return history.extrapolate(&cycle, target);
+        }
+        if dropped == target {
+            break;
+        }
+        problem = problem.drop_rock(rock, &mut jets_iter);
+    }
+    Ok(problem.tower_height())
+}
+
+/// Part 2, for any chamber and rocks.
+pub fn part2_with(config: &Config, jet_pattern: &[JetIndex]) -> AnyResult<usize> {
+    tower_height_by_cycle(config, jet_pattern, PART2_ROCKS)
+}
+
+fn part2(jet_pattern: &[JetIndex]) -> AnyResult<usize> {
+    part2_with(&Config::standard(), jet_pattern)
+}
+
+pub struct Day17;
+impl Solution for Day17 {
+    type Input = JetPattern;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input)
+    }
+}
+
+pub const DAY: Day = Day::new::<Day17>(17, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_correct() {
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        println!("{:?}", pattern);
+    }
+
+    #[test]
+    fn unrecognised_character_located() {
+        let err = parse_input(">><>x<\n").unwrap_err();
+        let err = err.downcast_ref::<parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "x"));
+    }
+
+    #[test]
+    fn part1_correct() {
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&pattern);
+        assert_eq!(res, 3068);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&pattern).unwrap();
+        assert_eq!(res, 1514285714288);
+    }
+
+    #[test]
+    fn rocks_parsed() {
+        let rocks = parse_rocks(STANDARD_ROCKS).unwrap();
+        assert_eq!(rocks.len(), 5);
+        assert_eq!(rocks[1].to_string(), ".#.\n###\n.#.\n");
+        assert_eq!((rocks[3].width(), rocks[3].height()), (1, 4));
+        let plus = [false, true, false];
+        let bar = [true; 3];
+        assert_eq!(
+            Rock::new(&[plus.to_vec(), bar.to_vec(), plus.to_vec()]).unwrap(),
+            rocks[1]
+        );
+    }
+
+    #[test]
+    fn bad_rocks_located() {
+        for (input, expected) in [
+            ("##\n#x\n", (2, 2, "x")),
+            ("##\n#\n", (2, 1, "#")),
+            ("#\n\n..\n", (3, 1, "..")),
+            ("#.\n.#\n..\n", (3, 1, "..")),
+        ] {
+            let err = parse_rocks(input).unwrap_err();
+            let err = err.downcast::<parse::ParseError>().unwrap();
+            assert_eq!((err.line, err.column, err.text.as_str()), expected);
+        }
+        assert!(parse_rocks("\n\n").is_err());
+    }
+
+    #[test]
+    fn rocks_must_fit() {
+        let rocks = parse_rocks(STANDARD_ROCKS).unwrap();
+        assert!(Config::new(5, rocks.clone(), 2, 3).is_err());
+        assert!(Config::new(6, rocks, 2, 3).is_ok());
+        assert!(Config::new(7, vec![], 2, 3).is_err());
+    }
+
+    #[test]
+    fn cycle_agrees_with_dropping_every_rock() {
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let rocks = parse_rocks(indoc! {"
+            ###
+            #..
+
+            .#
+            ##
+            .#
+
+            #####
+        "})
+        .unwrap();
+        for config in [
+            Config::standard(),
+            Config::new(9, rocks.clone(), 1, 2).unwrap(),
+            Config::new(5, rocks, 0, 4).unwrap(),
+        ] {
+            for target in [1, 17, 2022, 4321] {
+                assert_eq!(
+                    tower_height_by_cycle(&config, &pattern, target).unwrap(),
+                    tower_height(&config, &pattern, target)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_rows_pruned() {
+        let config = Config::standard();
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let mut jets_iter = pattern.iter().cycle().copied();
+        let mut problem = Problem::new(&config);
+        for rock in config.rocks.iter().cycle().take(10_000) {
+            problem = problem.drop_rock(rock, &mut jets_iter);
+            assert!(problem.rows.len() < 1000);
+        }
+        assert!(problem.pruned > 0);
+        assert_eq!(
+            problem.tower_height(),
+            tower_height(&config, &pattern, 10_000)
+        );
+    }
+
+    /// A check of the cycle shortcut far past the few thousand rocks that
+    /// [`cycle_agrees_with_dropping_every_rock`] covers; run it with
+    /// `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "a million rocks is slow to drop in debug mode"]
+    fn million_rocks_agree_with_cycle() {
+        let config = Config::standard();
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let target = 1_000_000;
+        assert_eq!(
+            tower_height(&config, &pattern, target),
+            tower_height_by_cycle(&config, &pattern, target).unwrap()
+        );
+    }
+
+    /// The same check as [`million_rocks_agree_with_cycle`], a thousand
+    /// times further on; run it with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore = "a billion rocks takes too long to drop in debug mode"]
+    fn billion_rocks_agree_with_cycle() {
+        let config = Config::standard();
+        let pattern = parse_input(TEST_INPUT).unwrap();
+        let target = 1_000_000_000;
+        assert_eq!(
+            tower_height(&config, &pattern, target),
+            tower_height_by_cycle(&config, &pattern, target).unwrap()
+        );
+    }
+
+    #[test]
+    fn deep_landings_change_the_cycle() {
+        // once a rock lands deeper than any before it, towers that looked the
+        // same no higher up carry on differently
+        let pattern = parse_input(">><>><>><<<><>>").unwrap();
+        let rocks = parse_rocks("#\n#\n").unwrap();
+        let config = Config::new(9, rocks, 7, 4).unwrap();
+        for target in [1000, 2500] {
+            assert_eq!(
+                tower_height_by_cycle(&config, &pattern, target).unwrap(),
+                tower_height(&config, &pattern, target)
+            );
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+
+use common::{search::bfs, *};
+
+pub type Pos = [i64; 3];
+
+const NEIGHBOUR_OFFSETS: [Pos; 6] = [
+    [-1, 0, 0],
+    [1, 0, 0],
+    [0, -1, 0],
+    [0, 1, 0],
+    [0, 0, -1],
+    [0, 0, 1],
+];
+
+fn parse_input(input: &str) -> AnyResult<Vec<Pos>> {
+    let src = parse::Source::new(input);
+    let mut cubes = vec![];
+    for l in input.lines() {
+        let coords = l
+            .split(',')
+            .map(|field| src.parse::<i64>(field))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pos = src.require(
+            coords.try_into().ok(),
+            l,
+            "expected three coordinates 'x,y,z'",
+        )?;
+        cubes.push(pos);
+    }
+    Ok(cubes)
+}
+
+fn neighbours(pos: Pos) -> impl Iterator<Item = Pos> {
+    NEIGHBOUR_OFFSETS
+        .map(|offset| [0, 1, 2].map(|i| pos[i] + offset[i]))
+        .into_iter()
+}
+
+/// The 3x3x3 block of positions centred on `pos`.
+fn block(pos: Pos) -> impl Iterator<Item = Pos> {
+    (-1..=1).flat_map(move |x| {
+        (-1..=1).flat_map(move |y| (-1..=1).map(move |z| [pos[0] + x, pos[1] + y, pos[2] + z]))
+    })
+}
+
+/// Cubes kept as a set of their positions, so that they can be anywhere.
+#[derive(Debug, Clone)]
+pub struct Voxels {
+    cells: HashSet<Pos>,
+}
+
+impl Voxels {
+    pub fn new(cubes: &[Pos]) -> Self {
+        Voxels {
+            cells: cubes.iter().copied().collect(),
+        }
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.cells.contains(pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The faces of cubes that aren't against another cube.
+    pub fn surface_area(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|&c| neighbours(c))
+            .filter(|n| !self.contains(n))
+            .count()
+    }
+
+    /// The faces of cubes that can be reached from outside: those of groups
+    /// not shut inside another group, facing air not shut in by their own.
+    pub fn exterior_surface_area(&self) -> usize {
+        let groups = self.groups();
+        let pockets: Vec<HashSet<Pos>> = groups.iter().map(Voxels::pocket_air).collect();
+
+        // the cubes along each line in x, to look along from one group for
+        // the walls of others
+        let mut lines: HashMap<[i64; 2], Vec<(i64, usize)>> = HashMap::new();
+        for (i, group) in groups.iter().enumerate() {
+            for c in &group.cells {
+                lines.entry([c[1], c[2]]).or_default().push((c[0], i));
+            }
+        }
+        for line in lines.values_mut() {
+            line.sort_unstable();
+        }
+
+        // a group shut inside another is shut in on every side, so looking
+        // along x from any one of its cubes, the first wall met of the group
+        // around it has pocket air in front of it
+        let enclosed = |i: usize| {
+            let Some(&[x, y, z]) = groups[i].cells.iter().next() else {
+                return false;
+            };
+            let mut walls_met = HashSet::new();
+            lines[&[y, z]]
+                .iter()
+                .filter(|&&(wall_x, j)| wall_x > x && j != i && walls_met.insert(j))
+                .any(|&(wall_x, j)| pockets[j].contains(&[wall_x - 1, y, z]))
+        };
+
+        groups
+            .iter()
+            .zip(&pockets)
+            .enumerate()
+            .filter(|&(i, _)| !enclosed(i))
+            .map(|(_, (group, pocket))| {
+                group
+                    .cells
+                    .iter()
+                    .flat_map(|&c| neighbours(c))
+                    .filter(|n| !group.contains(n) && !pocket.contains(n))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The cubes split into groups that touch, even only along an edge or
+    /// at a corner. Air can't squeeze between cubes that touch like that,
+    /// so each trapped pocket of air is closed in by a single group.
+    fn groups(&self) -> Vec<Voxels> {
+        let mut ungrouped = self.cells.clone();
+        let mut groups = vec![];
+        while let Some(&start) = ungrouped.iter().next() {
+            let touching = |&u: &Pos| block(u).filter(|v| self.contains(v));
+            let group: Vec<Pos> = bfs([start], touching, |_| false)
+                .costs()
+                .keys()
+                .copied()
+                .collect();
+            for cube in &group {
+                ungrouped.remove(cube);
+            }
+            groups.push(Voxels::new(&group));
+        }
+        groups
+    }
+
+    /// The empty cells next to a group of cubes, diagonals included, that
+    /// the group shuts in. The faces between the group and any one pocket
+    /// of air can all be reached from each other going round the cubes no
+    /// further out than that, so there's no need to fill the whole box
+    /// around the group, which could be vast even for a few cubes.
+    fn pocket_air(&self) -> HashSet<Pos> {
+        let Some(&[x, y, z]) = self.cells.iter().min() else {
+            return HashSet::new();
+        };
+        let around: HashSet<Pos> = self
+            .cells
+            .iter()
+            .flat_map(|&c| block(c))
+            .filter(|p| !self.contains(p))
+            .collect();
+        // nothing is further out in x than this, so it must be outside
+        let outside = [x - 1, y, z];
+        let successors = |&u: &Pos| neighbours(u).filter(|v| around.contains(v));
+        let exterior = bfs([outside], successors, |_| false);
+        around
+            .iter()
+            .filter(|p| exterior.cost(p).is_none())
+            .copied()
+            .collect()
+    }
+}
+
+fn part1(points: &[Pos]) -> usize {
+    Voxels::new(points).surface_area()
+}
+
+/// Split the cubes into groups that touch, and find the air each group shuts
+/// in just around itself, rather than filling the whole box around them.
+/// Faces onto that air don't count, and neither do groups shut inside
+/// another group.
+fn part2(points: &[Pos]) -> usize {
+    Voxels::new(points).exterior_surface_area()
+}
+
+pub struct Day18;
+impl Solution for Day18 {
+    type Input = Vec<Pos>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day18>(18, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use itertools::Itertools;
+
+    const TEST_INPUT: &str = indoc! {"
+        2,2,2
+        1,2,2
+        3,2,2
+        2,1,2
+        2,3,2
+        2,2,1
+        2,2,3
+        2,2,4
+        2,2,6
+        1,2,5
+        3,2,5
+        2,1,5
+        2,3,5
+    "};
+
+    #[test]
+    fn parse_input_correct2() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        for i in &input {
+            println!("{i:?}");
+        }
+        assert_eq!(input.len(), 13);
+    }
+
+    #[test]
+    fn part1_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&input);
+        assert_eq!(res, 64);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&input);
+        assert_eq!(res, 58);
+    }
+
+    fn shifted(input: &str, by: i64) -> String {
+        input
+            .lines()
+            .map(|l| {
+                l.split(',')
+                    .map(|v| v.parse::<i64>().unwrap() + by)
+                    .join(",")
+            })
+            .join("\n")
+    }
+
+    #[test]
+    fn far_away_cubes() {
+        for by in [-1000, 1_000_000, -4_000_000_000] {
+            let input = parse_input(&shifted(TEST_INPUT, by)).unwrap();
+            assert_eq!((part1(&input), part2(&input)), (64, 58), "shifted by {by}");
+        }
+        // two cubes a long way apart don't fill the space between them
+        let input = parse_input("-1000,0,0\n1000000,0,0\n").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (12, 12));
+    }
+
+    #[test]
+    fn malformed_lines_located() {
+        for (input, expected) in [
+            ("1,2,3\n1,2\n", (2, 1, "1,2")),
+            ("1,2,3\n1,2,3,4\n", (2, 1, "1,2,3,4")),
+            ("1,x,3\n", (1, 3, "x")),
+            ("1,,3\n", (1, 3, "")),
+        ] {
+            let err = parse_input(input).unwrap_err();
+            let err = err.downcast::<parse::ParseError>().unwrap();
+            assert_eq!((err.line, err.column, err.text.as_str()), expected);
+        }
+    }
+
+    #[test]
+    fn cube_inside_a_hollow_shell() {
+        // a hollow 5x5x5 box, with a cube floating in the middle of it
+        let mut cubes: Vec<Pos> = (0..5)
+            .flat_map(|x| (0..5).flat_map(move |y| (0..5).map(move |z| [x, y, z])))
+            .filter(|p| p.iter().any(|&v| v == 0 || v == 4))
+            .collect();
+        cubes.push([2, 2, 2]);
+        assert_eq!(part1(&cubes), 150 + 54 + 6);
+        assert_eq!(part2(&cubes), 150);
+    }
+
+    fn hollow_box(corner: i64, size: i64) -> impl Iterator<Item = Pos> {
+        let range = corner..corner + size;
+        let edge = [corner, corner + size - 1];
+        range
+            .clone()
+            .cartesian_product(range.clone())
+            .cartesian_product(range)
+            .map(|((x, y), z)| [x, y, z])
+            .filter(move |p| p.iter().any(|v| edge.contains(v)))
+    }
+
+    #[test]
+    fn boxes_inside_boxes() {
+        // a cube inside a 5x5x5 box, inside a 9x9x9 box
+        let mut cubes: Vec<Pos> = hollow_box(0, 9).chain(hollow_box(2, 5)).collect();
+        cubes.push([4, 4, 4]);
+        assert_eq!(part2(&cubes), 6 * 9 * 9);
+    }
+
+    #[test]
+    fn corner_touching_chain() {
+        // each cube only touches the next at a corner, so every face shows,
+        // and the box around them all is far too big to fill
+        let cubes: Vec<Pos> = (0..10_000).map(|i| [i, i, i]).collect();
+        assert_eq!((part1(&cubes), part2(&cubes)), (60_000, 60_000));
+    }
+
+    /// The exterior surface area found by filling the whole box around the
+    /// cubes, to check against.
+    fn flooded_exterior_surface_area(cubes: &[Pos]) -> usize {
+        let voxels = Voxels::new(cubes);
+        let min = [0, 1, 2].map(|i| cubes.iter().map(|c| c[i]).min().unwrap() - 1);
+        let max = [0, 1, 2].map(|i| cubes.iter().map(|c| c[i]).max().unwrap() + 1);
+        let in_bounds = |p: &Pos| (0..3).all(|i| min[i] <= p[i] && p[i] <= max[i]);
+        let successors = |&u: &Pos| neighbours(u).filter(|v| in_bounds(v) && !voxels.contains(v));
+        let exterior = bfs([min], successors, |_| false);
+        cubes
+            .iter()
+            .flat_map(|&c| neighbours(c))
+            .filter(|n| exterior.cost(n).is_some())
+            .count()
+    }
+
+    #[test]
+    fn matches_filling_the_box() {
+        let mut seed = 7_u64;
+        let mut random = |below: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) % below) as i64
+        };
+        for density in [20, 40, 50, 60] {
+            for _ in 0..10 {
+                let mut cubes: Vec<Pos> = hollow_box(0, 8).filter(|_| random(100) < 90).collect();
+                cubes.extend(
+                    (0..8)
+                        .cartesian_product(0..8)
+                        .cartesian_product(0..8)
+                        .map(|((x, y), z)| [x, y, z])
+                        .filter(|_| random(100) < density),
+                );
+                let cubes: Vec<Pos> = cubes.into_iter().unique().collect();
+                assert_eq!(
+                    part2(&cubes),
+                    flooded_exterior_surface_area(&cubes),
+                    "{cubes:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! A search engine for build-order problems like the robot factories, with
+//! any number of resource types given at runtime.
+//!
+//! Each robot collects one unit of its own resource every minute, and costs
+//! some amounts of resources to build. One robot can be built per minute,
+//! and it starts collecting the minute after it is started. The search
+//! either maximises the amount of a resource collected by a time limit, or
+//! finds the earliest time a target amount can be collected.
+
+use anyhow::bail;
+use arrayvec::ArrayVec;
+use common::AnyResult;
+use priority_queue::PriorityQueue;
+use rustc_hash::FxHashMap;
+use std::{cmp::Reverse, collections::hash_map::Entry};
+
+use crate::schedule::Schedule;
+
+pub const MAX_RESOURCES: usize = 8;
+
+/// An amount of each resource, or of robots collecting each resource.
+/// Only the first [`Economy::len`] are used; the rest stay zero.
+pub type Amounts = [i32; MAX_RESOURCES];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Economy {
+    pub names: Vec<String>,
+    /// `costs[r][s]` is how much of resource `s` a robot collecting
+    /// resource `r` costs.
+    pub costs: Vec<Amounts>,
+    pub initial_robots: Amounts,
+}
+
+impl Economy {
+    pub fn new(names: &[&str], costs: &[Vec<i32>], initial_robots: &[i32]) -> AnyResult<Self> {
+        let n = names.len();
+        if n > MAX_RESOURCES {
+            bail!("at most {MAX_RESOURCES} resources are supported, not {n}");
+        }
+        if costs.len() != n || costs.iter().any(|c| c.len() != n) || initial_robots.len() != n {
+            bail!("costs and initial robots must be given for each of the {n} resources");
+        }
+        if costs.iter().flatten().chain(initial_robots).any(|&x| x < 0) {
+            bail!("costs and initial robots can't be negative");
+        }
+        Ok(Economy {
+            names: names.iter().map(|&s| s.to_owned()).collect(),
+            costs: costs.iter().map(|c| amounts(c)).collect(),
+            initial_robots: amounts(initial_robots),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn resource(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+fn amounts(values: &[i32]) -> Amounts {
+    let mut amounts = [0; MAX_RESOURCES];
+    amounts[..values.len()].copy_from_slice(values);
+    amounts
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Collect as much of this resource as possible by the time limit.
+    Maximise(usize),
+    /// Collect `amount` of `resource` as early as possible.
+    Reach { resource: usize, amount: i32 },
+}
+impl Objective {
+    pub fn resource(&self) -> usize {
+        match *self {
+            Objective::Maximise(resource) | Objective::Reach { resource, .. } => resource,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Spec {
+    pub economy: Economy,
+    pub max_time: usize,
+    pub objective: Objective,
+    pub maximum_useful_robots: Amounts,
+    /// Whether to build a robot for the objective whenever one can be
+    /// afforded, without trying anything else; off unless asked for.
+    pub objective_first: bool,
+}
+impl Spec {
+    pub fn new(economy: Economy, max_time: usize, objective: Objective) -> AnyResult<Self> {
+        let resource = objective.resource();
+        if resource >= economy.len() {
+            bail!(
+                "the objective is resource {resource}, but there are only {}",
+                economy.len()
+            );
+        }
+
+        // calculate maximum possible consumption rate, given that we can
+        // only build a single robot each minute: this is the max of resources
+        // required across all robots, and dictates the maximum number of
+        // useful robots of each type.
+        let mut maximum_useful_robots: Amounts =
+            std::array::from_fn(|r| economy.costs.iter().map(|c| c[r]).max().unwrap_or(0));
+        // no limit on robots collecting what we're after
+        maximum_useful_robots[resource] = i32::MAX;
+
+        Ok(Spec {
+            economy,
+            max_time,
+            objective,
+            maximum_useful_robots,
+            objective_first: false,
+        })
+    }
+
+    /// The same spec, but searching only schedules that build a robot for
+    /// the objective whenever they can. That's a good rule for the
+    /// puzzle's geodes, where nothing else is worth more, but with other
+    /// costs it can pass up a cheaper robot that would pay off sooner.
+    pub fn objective_first(self) -> Self {
+        Spec {
+            objective_first: true,
+            ..self
+        }
+    }
+
+    pub fn initial_state(&self) -> State {
+        State {
+            time: 0,
+            resources: [0; MAX_RESOURCES],
+            robots: self.economy.initial_robots,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash)]
+pub struct State {
+    pub time: usize,
+    pub resources: Amounts,
+    pub robots: Amounts,
+}
+impl State {
+    pub fn advance(&self) -> State {
+        State {
+            time: self.time + 1,
+            resources: std::array::from_fn(|r| self.resources[r] + self.robots[r]),
+            ..*self
+        }
+    }
+}
+
+pub fn try_advance_with(spec: &Spec, state: &State, new_robot: usize) -> Option<State> {
+    let cost = &spec.economy.costs[new_robot];
+
+    // insufficient resources - not a possible move
+    if state
+        .resources
+        .iter()
+        .zip(cost)
+        .any(|(have, need)| have < need)
+    {
+        return None;
+    }
+
+    // collect with the existing robots, then add the new robot _last_,
+    // since it only starts collecting next minute; the order is important.
+    let mut new_state = state.advance();
+    for (have, need) in new_state.resources.iter_mut().zip(cost) {
+        *have -= need;
+    }
+    new_state.robots[new_robot] += 1;
+
+    Some(new_state)
+}
+
+pub type PossibleStates = ArrayVec<State, { MAX_RESOURCES + 1 }>;
+
+pub fn possible_states_from(spec: &Spec, state: &State) -> PossibleStates {
+    let mut possible = ArrayVec::new();
+    let target = spec.objective.resource();
+
+    // try building a robot for the objective first; if the spec says to,
+    // only consider other alternatives if we can't do this.
+    let objective_robot = try_advance_with(spec, state, target);
+    if let Some(s) = objective_robot {
+        possible.push(s);
+    }
+    if objective_robot.is_none() || !spec.objective_first {
+        for robot in (0..spec.economy.len()).rev().filter(|&r| r != target) {
+            // check that we're below the limit for the number of robots of this type:
+            // there's no point building more than required.
+            if state.robots[robot] >= spec.maximum_useful_robots[robot] {
+                continue;
+            }
+
+            // otherwise consider building one if possible
+            if let Some(s) = try_advance_with(spec, state, robot) {
+                possible.push(s);
+            }
+        }
+
+        // add time advance with no new robots
+        possible.push(state.advance());
+    }
+
+    possible
+}
+
+/// Very basic bound condition - estimate the maximum theoretically possible
+/// amount of `resource` collected after `time` more minutes, assuming that we
+/// add a new robot for it every minute (regardless of the number of actual
+/// other robots present). Think `s = ut + 1/2at^2`
+pub fn max_potential(state: &State, resource: usize, time: usize) -> i64 {
+    let u = state.robots[resource] as i64;
+    let t = time as i64;
+    state.resources[resource] as i64 + u * t + t * (t - 1) / 2
+}
+
+/// The fewest minutes in which `amount` of `resource` could possibly be
+/// collected, by the same optimistic bound.
+fn min_time_to_reach(state: &State, resource: usize, amount: i32) -> usize {
+    (0..)
+        .find(|&t| max_potential(state, resource, t) >= amount as i64)
+        .expect("a robot every minute eventually collects any amount")
+}
+
+/// Search for the best final state for the objective, prioritising states
+/// by an optimistic bound on what they could achieve; the bound is also
+/// used to reject states that cannot possibly improve on the best so far.
+///
+/// When maximising, the best state is at the time limit. When reaching a
+/// target, it is the earliest state to collect the target amount, if that
+/// can be done within the time limit. Either way, the schedule of robot
+/// builds that leads to it is returned.
+pub fn optimise(spec: &Spec) -> Option<Schedule> {
+    // the state each state was first reached from, to retrace the builds
+    let mut came_from: FxHashMap<State, State> = FxHashMap::default();
+    let best = match spec.objective {
+        Objective::Maximise(resource) => maximise(spec, resource, &mut came_from),
+        Objective::Reach { resource, amount } => reach(spec, resource, amount, &mut came_from),
+    };
+    best.map(|end| Schedule::retrace(&came_from, end))
+}
+
+fn maximise(
+    spec: &Spec,
+    resource: usize,
+    came_from: &mut FxHashMap<State, State>,
+) -> Option<State> {
+    let mut global_best: Option<State> = None;
+
+    let mut queue: PriorityQueue<State, i64> = PriorityQueue::new();
+
+    let potential = |s: &State| max_potential(s, resource, spec.max_time - s.time);
+    let initial = spec.initial_state();
+    let initial_potential = potential(&initial);
+    queue.push(initial, initial_potential);
+    while let Some((v, _prio)) = queue.pop() {
+        // terminal node
+        if v.time == spec.max_time {
+            // update best
+            if global_best
+                .as_ref()
+                .is_none_or(|best| v.resources[resource] > best.resources[resource])
+            {
+                global_best = Some(v);
+            }
+            continue;
+        }
+
+        // explore from here
+        for w in possible_states_from(spec, &v) {
+            if let Entry::Vacant(entry) = came_from.entry(w) {
+                entry.insert(v);
+
+                // check if next state _could_ be better than our existing global best; skip if not
+                let max_potential = potential(&w);
+                if let Some(best) = &global_best {
+                    if max_potential <= best.resources[resource] as i64 {
+                        continue;
+                    }
+                }
+
+                // otherwise add to queue for exploration
+                queue.push(w, max_potential);
+            }
+        }
+    }
+
+    global_best
+}
+
+fn reach(
+    spec: &Spec,
+    resource: usize,
+    amount: i32,
+    came_from: &mut FxHashMap<State, State>,
+) -> Option<State> {
+    let mut queue: PriorityQueue<State, Reverse<usize>> = PriorityQueue::new();
+
+    // the priority is the earliest possible finish, reversed so that the
+    // earliest comes first; it never overestimates, so the first state to
+    // come out of the queue with the target amount is the earliest
+    let earliest = |s: &State| s.time + min_time_to_reach(s, resource, amount);
+    let initial = spec.initial_state();
+    if earliest(&initial) > spec.max_time {
+        return None;
+    }
+    let initial_earliest = earliest(&initial);
+    queue.push(initial, Reverse(initial_earliest));
+    while let Some((v, _prio)) = queue.pop() {
+        if v.resources[resource] >= amount {
+            return Some(v);
+        }
+
+        for w in possible_states_from(spec, &v) {
+            if let Entry::Vacant(entry) = came_from.entry(w) {
+                entry.insert(v);
+                let finish = earliest(&w);
+                if finish <= spec.max_time {
+                    queue.push(w, Reverse(finish));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // three resources: wood, planks made from wood, and houses from both
+    fn village() -> Economy {
+        Economy::new(
+            &["wood", "planks", "houses"],
+            &[vec![2, 0, 0], vec![3, 0, 0], vec![2, 4, 0]],
+            &[1, 0, 0],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rejects_bad_economies() {
+        assert!(Economy::new(&["a", "b"], &[vec![1, 0]], &[1, 0]).is_err());
+        assert!(Economy::new(&["a"], &[vec![-1]], &[1]).is_err());
+        let names = ["x"; MAX_RESOURCES + 1];
+        let costs = vec![vec![0; names.len()]; names.len()];
+        assert!(Economy::new(&names, &costs, &vec![0; names.len()]).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_objectives() {
+        assert!(Spec::new(village(), 10, Objective::Maximise(3)).is_err());
+        let objective = Objective::Reach {
+            resource: 3,
+            amount: 1,
+        };
+        assert!(Spec::new(village(), 10, objective).is_err());
+    }
+
+    #[test]
+    fn caps_useful_robots() {
+        let spec = Spec::new(village(), 10, Objective::Maximise(2)).unwrap();
+        assert_eq!(spec.maximum_useful_robots[..3], [3, 4, i32::MAX]);
+    }
+
+    #[test]
+    fn maximises_any_resource() {
+        // a single wood robot built in minute 3, spending 2 wood
+        let spec = Spec::new(village(), 6, Objective::Maximise(0)).unwrap();
+        let best = optimise(&spec).unwrap().end();
+        assert_eq!(best.time, 6);
+        assert_eq!(best.robots[0], 2);
+        assert_eq!(best.resources[0], 7);
+
+        // building one whenever possible, in minutes 3, 5 and 6, spends
+        // too much on robots that haven't time to pay for themselves
+        let greedy = optimise(&spec.objective_first()).unwrap().end();
+        assert_eq!(greedy.robots[0], 4);
+        assert_eq!(greedy.resources[0], 4);
+    }
+
+    #[test]
+    fn reaches_target_earliest() {
+        let objective = Objective::Reach {
+            resource: 2,
+            amount: 1,
+        };
+        let best = optimise(&Spec::new(village(), 30, objective).unwrap())
+            .unwrap()
+            .end();
+        assert_eq!(best.resources[2], 1);
+        assert_eq!(best.time, 10);
+
+        // and not at all if there isn't time
+        assert!(optimise(&Spec::new(village(), 9, objective).unwrap()).is_none());
+    }
+
+    #[test]
+    fn reach_agrees_with_maximise() {
+        let spec = Spec::new(village(), 14, Objective::Maximise(2)).unwrap();
+        let most = optimise(&spec).unwrap().end().resources[2];
+        let objective = Objective::Reach {
+            resource: 2,
+            amount: most,
+        };
+        assert_eq!(
+            optimise(&Spec::new(village(), 14, objective).unwrap())
+                .unwrap()
+                .end()
+                .time,
+            14
+        );
+        let objective = Objective::Reach {
+            resource: 2,
+            amount: most + 1,
+        };
+        assert!(optimise(&Spec::new(village(), 14, objective).unwrap()).is_none());
+    }
+
+    #[test]
+    fn unreachable_amounts_dont_overflow() {
+        let objective = Objective::Reach {
+            resource: 2,
+            amount: i32::MAX,
+        };
+        assert!(optimise(&Spec::new(village(), 30, objective).unwrap()).is_none());
+    }
+}
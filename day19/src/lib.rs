@@ -1,12 +1,14 @@
+pub mod economy;
 pub mod parser;
+pub mod schedule;
 
-use arrayvec::ArrayVec;
+use anyhow::{anyhow, bail};
+use common::{AnyResult, Day, Solution};
+use economy::{max_potential, optimise, possible_states_from, Economy, Objective, Spec, State};
 use indoc::indoc;
-use nalgebra::Vector4;
-use priority_queue::PriorityQueue;
-use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use rustc_hash::FxHashSet;
-use std::{hash::Hash, str::FromStr, time::Instant};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use schedule::Schedule;
+use std::{fmt::Write, str::FromStr};
 use Mineral::*;
 
 pub const TEST_INPUT: &str = indoc! {"
@@ -47,36 +49,29 @@ pub struct Blueprint {
     pub geode_robot: Cost,
 }
 impl Blueprint {
-    pub fn to_spec(&self, max_time: usize) -> BlueprintSpec {
-        let ore_robot = self.ore_robot.to_spec();
-        let clay_robot = self.clay_robot.to_spec();
-        let obsidian_robot = self.obsidian_robot.to_spec();
-        let geode_robot = self.geode_robot.to_spec();
-
-        // calculate maximum possible consumption rate, given that we can
-        // only build a single robot each minute: this is the max of resources
-        // required across all robots, and dictates the maximum number of
-        // useful robots of each type.
-        let mut maximum_useful_robots = Vector4::zeros();
-        for i in 0..3 {
-            maximum_useful_robots[i] = ore_robot[i]
-                .max(clay_robot[i])
-                .max(obsidian_robot[i])
-                .max(geode_robot[i]);
-        }
-        // no limit on geode-producing robots
-        maximum_useful_robots[3] = i32::MAX;
-
-        //dbg!(&maximum_useful_robots);
+    /// The blueprint's robots, starting with a single ore robot.
+    pub fn economy(&self) -> Economy {
+        let costs = [
+            self.ore_robot,
+            self.clay_robot,
+            self.obsidian_robot,
+            self.geode_robot,
+        ]
+        .map(|c| c.to_vec());
+        Economy::new(&["ore", "clay", "obsidian", "geode"], &costs, &[1, 0, 0, 0])
+            .expect("four minerals with non-negative costs")
+    }
 
-        BlueprintSpec {
+    /// The spec for collecting as many geodes as possible by `max_time`,
+    /// building a geode robot whenever one can be afforded.
+    pub fn to_spec(&self, max_time: usize) -> Spec {
+        Spec::new(
+            self.economy(),
             max_time,
-            ore_robot,
-            clay_robot,
-            obsidian_robot,
-            geode_robot,
-            maximum_useful_robots,
-        }
+            Objective::Maximise(Geode as usize),
+        )
+        .expect("geode is one of the four minerals")
+        .objective_first()
     }
 }
 
@@ -87,139 +82,16 @@ pub struct Cost {
     pub obsidian: i32,
 }
 impl Cost {
-    pub fn to_spec(&self) -> Vector4<i32> {
-        Vector4::new(self.ore, self.clay, self.obsidian, 0)
-    }
-}
-
-#[derive(Debug, Clone, Default, Copy, PartialEq, PartialOrd, Hash)]
-pub struct BlueprintSpec {
-    pub max_time: usize,
-    pub ore_robot: Vector4<i32>,
-    pub clay_robot: Vector4<i32>,
-    pub obsidian_robot: Vector4<i32>,
-    pub geode_robot: Vector4<i32>,
-    pub maximum_useful_robots: Vector4<i32>,
-}
-impl BlueprintSpec {
-    pub fn required_resources(&self, robot: Mineral) -> &Vector4<i32> {
-        match robot {
-            Ore => &self.ore_robot,
-            Clay => &self.clay_robot,
-            Obsidian => &self.obsidian_robot,
-            Geode => &self.geode_robot,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Default, Copy, PartialEq, PartialOrd, Hash, Eq)]
-pub struct State {
-    time: usize,
-    resources: Vector4<i32>,
-    robots: Vector4<i32>,
-}
-impl State {
-    pub fn advance(&self) -> State {
-        let resources = self.resources + self.robots;
-        let time = self.time + 1;
-        State {
-            resources,
-            time,
-            ..*self
-        }
-    }
-
-    pub fn new() -> State {
-        State {
-            time: 0,
-            resources: Vector4::new(0, 0, 0, 0),
-            robots: Vector4::new(1, 0, 0, 0),
-        }
-    }
-}
-
-pub fn try_advance_with(spec: &BlueprintSpec, state: &State, new_robot: Mineral) -> Option<State> {
-    let resources_required = spec.required_resources(new_robot);
-    let resources_after = state.resources - resources_required;
-
-    // insufficient resources - not a possible move
-    if resources_after.min() < 0 {
-        return None;
-    }
-
-    // increment robot
-    let mut robots_after = state.robots;
-    robots_after[new_robot as usize] += 1;
-
-    // update resources and advance state
-    let mut new_state = State {
-        resources: resources_after,
-        ..*state
-    }
-    .advance();
-
-    // then finally update the number of robots _last_, after advancing
-    // the state; the order is important here.
-    new_state.robots = robots_after;
-
-    Some(new_state)
-}
-
-pub type PossibleStates = ArrayVec<State, 5>;
-
-pub fn possible_states_from(spec: &BlueprintSpec, state: &State) -> PossibleStates {
-    let mut possible = ArrayVec::new();
-
-    // try building Geode robot; and this is greedy -- only consider other alternatives
-    // if we can't do this.
-    if let Some(s) = try_advance_with(spec, state, Geode) {
-        possible.push(s);
-    } else {
-        for robot in [Obsidian, Clay, Ore] {
-            // check that we're below the limit for the number of robots of this type:
-            // there's no point building more than required.
-            let maximum_useful = spec.maximum_useful_robots[robot as usize];
-            if state.robots[robot as usize] >= maximum_useful {
-                continue;
-            }
-
-            // otherwise consider building one if possible
-            if let Some(s) = try_advance_with(spec, state, robot) {
-                possible.push(s);
-            }
-        }
-
-        // add time advance with no new robots
-        possible.push(state.advance());
+    pub fn to_vec(&self) -> Vec<i32> {
+        vec![self.ore, self.clay, self.obsidian, 0]
     }
-
-    possible
-}
-
-/// Very basic bound condition - estimate the maximum theoretically possible
-/// number of geodes producible at the end of the simulation from this State,
-/// assuming that we add a new geode factory on every iteration (regardless of
-/// the number of actual other factories present). Think `s = ut + 1/2at^2`
-pub fn simple_max_potential_geodes(state: &State, max_time: usize) -> i32 {
-    let remaining_time = max_time - state.time;
-    let geodes = state.resources[Geode as usize];
-    let robots = state.robots[Geode as usize];
-
-    let u = robots;
-    let t = remaining_time as i32;
-    let manufactured = u * t + t * (t - 1) / 2;
-
-    geodes + manufactured
 }
 
 /// Original recursive DFS I used to solve the problem. I've replaced it with a faster
 /// priority-queue based version below.
 #[allow(dead_code)]
-pub fn explore_dfs_max_original(
-    spec: &BlueprintSpec,
-    state: &State,
-    global_best: &mut Option<State>,
-) {
+pub fn explore_dfs_max_original(spec: &Spec, state: &State, global_best: &mut Option<State>) {
+    let geodes = |s: &State| s.resources[Geode as usize];
     for next_state in possible_states_from(spec, state) {
         // skip if we're almost at termination and have no geode factories
         if next_state.time == spec.max_time - 1 && next_state.robots[Geode as usize] == 0 {
@@ -232,7 +104,7 @@ pub fn explore_dfs_max_original(
             // advance to final state
             let final_state = next_state.advance();
             if let Some(existing_best) = global_best {
-                if final_state.resources[Geode as usize] > existing_best.resources[Geode as usize] {
+                if geodes(&final_state) > geodes(existing_best) {
                     *existing_best = final_state;
                 }
             } else {
@@ -244,9 +116,9 @@ pub fn explore_dfs_max_original(
 
         // check if next state _could_ be better than our existing global best; skip if not
         if let Some(existing_best) = global_best {
-            let geodes = existing_best.resources[Geode as usize];
-            let potential = simple_max_potential_geodes(&next_state, spec.max_time);
-            if potential <= geodes {
+            let potential =
+                max_potential(&next_state, Geode as usize, spec.max_time - next_state.time);
+            if potential <= geodes(existing_best) as i64 {
                 continue;
             }
         }
@@ -256,96 +128,79 @@ pub fn explore_dfs_max_original(
     }
 }
 
-/// Fairly simple DFS using a priority queue based on an estimate
-/// for the maximum possible geodes from a given state; this is
-/// also used as an upper bound to reject states that cannot possibly
-/// improve on the existing best.
-fn explore_prio_max(spec: &BlueprintSpec) -> Option<State> {
-    let mut global_best = None;
-
-    let mut visited: FxHashSet<State> = FxHashSet::default();
-    let mut queue: PriorityQueue<State, i32> = PriorityQueue::new();
-
-    let initial = State::new();
-    queue.push(
-        initial,
-        simple_max_potential_geodes(&initial, spec.max_time),
-    );
-    while let Some((v, _prio)) = queue.pop() {
-        // terminal node
-        if v.time == spec.max_time {
-            // update best
-            let best = global_best.get_or_insert(v);
-            if v.resources[Geode as usize] > best.resources[Geode as usize] {
-                global_best.replace(v);
-            }
-            continue;
-        }
-
-        // explore from here
-        for w in possible_states_from(spec, &v) {
-            if !visited.contains(&w) {
-                visited.insert(w);
-
-                // check if next state _could_ be better than our existing global best; skip if not
-                let max_potential = simple_max_potential_geodes(&w, spec.max_time);
-                if let Some(best) = global_best {
-                    let geodes = best.resources[Geode as usize];
-                    let potential = max_potential;
-                    if potential <= geodes {
-                        continue;
-                    }
-                }
-
-                // otherwise add to queue for exploration
-                queue.push(w, max_potential);
-            }
-        }
-    }
-
-    global_best
-}
-
-pub fn part1(blueprints: &[Blueprint]) -> i32 {
-    let sum: i32 = blueprints
+pub fn part1(blueprints: &[Blueprint]) -> AnyResult<i32> {
+    let qualities = blueprints
         .par_iter()
         .map(|bp| {
-            let spec = bp.to_spec(TIME_MAX_PART1);
-            let best = explore_prio_max(&spec);
-
-            let id = bp.id;
-            let geodes = best.map(|b| b.resources[Geode as usize]).unwrap_or(0);
-            println!("part1 id {id} with {geodes} geodes");
-
-            id * geodes
+            let geodes = best_schedule(bp, TIME_MAX_PART1)?.end().resources[Geode as usize];
+            Ok(bp.id * geodes)
         })
-        .sum();
+        .collect::<AnyResult<Vec<i32>>>()?;
+    Ok(qualities.iter().sum())
+}
 
-    sum
+/// The schedule that collects the most geodes from `blueprint` in
+/// `max_time` minutes.
+pub fn best_schedule(blueprint: &Blueprint, max_time: usize) -> AnyResult<Schedule> {
+    optimise(&blueprint.to_spec(max_time)).ok_or_else(|| {
+        anyhow!(
+            "blueprint {} has no schedule lasting {max_time} minutes",
+            blueprint.id
+        )
+    })
 }
 
-pub fn part2(blueprints: &[Blueprint]) -> i32 {
-    let product: i32 = blueprints
+/// The blueprints part 2 looks at: only the first three.
+fn part2_blueprints(blueprints: &[Blueprint]) -> &[Blueprint] {
+    &blueprints[..blueprints.len().min(3)]
+}
+
+pub fn part2(blueprints: &[Blueprint]) -> AnyResult<i32> {
+    let geodes = part2_blueprints(blueprints)
         .par_iter()
-        .take(3)
-        .map(|bp| {
-            let t0 = Instant::now();
+        .map(|bp| Ok(best_schedule(bp, TIME_MAX_PART2)?.end().resources[Geode as usize]))
+        .collect::<AnyResult<Vec<i32>>>()?;
+    Ok(geodes.iter().product())
+}
 
-            let spec = bp.to_spec(TIME_MAX_PART2);
-            let best = explore_prio_max(&spec);
+/// The best schedule for every blueprint looked at in `part`, minute by
+/// minute.
+pub fn narrate_best_schedules(blueprints: &[Blueprint], part: u8) -> AnyResult<String> {
+    let (max_time, blueprints) = match part {
+        1 => (TIME_MAX_PART1, blueprints),
+        2 => (TIME_MAX_PART2, part2_blueprints(blueprints)),
+        _ => bail!("there's no part {part}"),
+    };
+    let mut text = String::new();
+    for bp in blueprints {
+        let schedule = best_schedule(bp, max_time)?;
+        writeln!(text, "\n==== Part {part}, blueprint {} ====", bp.id)?;
+        writeln!(text, "{}", schedule.narrate(&bp.economy()))?;
+    }
+    Ok(text)
+}
 
-            let id = bp.id;
-            let geodes = best.unwrap().resources[Geode as usize];
-            let elapsed = Instant::now() - t0;
-            println!("part2 id {id} with {geodes} geodes after {elapsed:#?}");
+pub struct Day19;
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-            geodes
-        })
-        .product();
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(input)
+    }
 
-    product
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input)
+    }
 }
 
+pub const DAY: Day = Day::new::<Day19>(19, env!("CARGO_MANIFEST_DIR"));
+
 #[cfg(test)]
 mod tests {
     use crate::{parser::parse_input, *};
@@ -359,7 +214,7 @@ mod tests {
     fn part1_blueprint1_original_correct() {
         let spec = blueprints()[0].to_spec(TIME_MAX_PART1);
         let mut best = None;
-        explore_dfs_max_original(&spec, &State::new(), &mut best);
+        explore_dfs_max_original(&spec, &spec.initial_state(), &mut best);
         let best = best.unwrap();
         assert_eq!(best.resources[Geode as usize], 9);
         assert_eq!(best.time, TIME_MAX_PART1);
@@ -368,7 +223,7 @@ mod tests {
     #[test]
     fn part1_blueprint1_prio_correct() {
         let spec = blueprints()[0].to_spec(TIME_MAX_PART1);
-        let best = explore_prio_max(&spec).unwrap();
+        let best = optimise(&spec).unwrap().end();
         assert_eq!(best.resources[Geode as usize], 9);
         assert_eq!(best.time, TIME_MAX_PART1);
     }
@@ -378,7 +233,7 @@ mod tests {
     fn part1_blueprint2_original_correct() {
         let spec = blueprints()[1].to_spec(TIME_MAX_PART1);
         let mut best = None;
-        explore_dfs_max_original(&spec, &State::new(), &mut best);
+        explore_dfs_max_original(&spec, &spec.initial_state(), &mut best);
         let best = best.unwrap();
         assert_eq!(best.resources[Geode as usize], 12);
         assert_eq!(best.time, TIME_MAX_PART1);
@@ -387,7 +242,7 @@ mod tests {
     #[test]
     fn part1_blueprint2_prio_correct() {
         let spec = blueprints()[1].to_spec(TIME_MAX_PART1);
-        let best = explore_prio_max(&spec).unwrap();
+        let best = optimise(&spec).unwrap().end();
         assert_eq!(best.resources[Geode as usize], 12);
         assert_eq!(best.time, TIME_MAX_PART1);
     }
@@ -395,14 +250,23 @@ mod tests {
     #[test]
     fn part1_correct() {
         let blueprints = parse_input(TEST_INPUT).unwrap();
-        let res = part1(&blueprints);
+        let res = part1(&blueprints).unwrap();
         assert_eq!(res, 33);
     }
 
+    #[test]
+    fn narrates_every_schedule() {
+        let text = narrate_best_schedules(&blueprints(), 1).unwrap();
+        assert!(text.starts_with("\n==== Part 1, blueprint 1 ====\n== Minute 1 =="));
+        assert!(text.contains("\n==== Part 1, blueprint 2 ====\n"));
+        assert_eq!(text.matches("== Minute 24 ==").count(), 2);
+        assert!(narrate_best_schedules(&blueprints(), 3).is_err());
+    }
+
     #[test]
     fn part2_blueprint1_correct() {
         let spec = blueprints()[0].to_spec(TIME_MAX_PART2);
-        let best = explore_prio_max(&spec).unwrap();
+        let best = optimise(&spec).unwrap().end();
         assert_eq!(best.resources[Geode as usize], 56);
         assert_eq!(best.time, TIME_MAX_PART2);
     }
@@ -410,7 +274,7 @@ mod tests {
     #[test]
     fn part2_blueprint2_correct() {
         let spec = blueprints()[1].to_spec(TIME_MAX_PART2);
-        let best = explore_prio_max(&spec).unwrap();
+        let best = optimise(&spec).unwrap().end();
         assert_eq!(best.resources[Geode as usize], 62);
         assert_eq!(best.time, TIME_MAX_PART2);
     }
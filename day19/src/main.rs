@@ -1,21 +1,22 @@
-use std::time::Instant;
+use common::{
+    input::{DayArgs, DayOption},
+    Solution,
+};
+use day19::Day19;
 
-use common::*;
-use day19::{parser::parse_input, part1, part2};
-
-fn main() -> AnyResult<()> {
-    let blueprints = parse_input(&read_file("day19/input.txt")?)?;
-
-    let t1 = Instant::now();
-    println!("note: this will take a while to run especially in debug mode; we expect 1834");
-    println!("part1 result = {}", part1(&blueprints));
-    println!("complete in {:#?}", Instant::now() - t1);
-
-    let t2 = Instant::now();
-    println!();
-    println!("note: this will take a while to run especially in debug mode; we expect 2240");
-    println!("part2 result = {}", part2(&blueprints));
-    println!("complete in {:#?}", Instant::now() - t2);
+/// `--schedule` prints, after the answers, the best build schedule for each
+/// blueprint in each part.
+const OPTIONS: &[DayOption] = &[DayOption::flag("schedule")];
 
+fn main() -> anyhow::Result<()> {
+    let args = DayArgs::from_env(OPTIONS)?;
+    let input = args.read_input(&day19::DAY)?;
+    common::print_answers(&day19::DAY, &input)?;
+    if args.flag("schedule") {
+        let blueprints = Day19::parse(&input)?;
+        for part in 1..=2 {
+            print!("{}", day19::narrate_best_schedules(&blueprints, part)?);
+        }
+    }
     Ok(())
 }
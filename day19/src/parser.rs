@@ -1,45 +1,71 @@
-use anyhow::bail;
-use common::*;
+use common::{
+    parse::{ParseError, Source},
+    *,
+};
 use regex::Regex;
 
 use crate::{Blueprint, Cost, Mineral};
 use Mineral::*;
 
 pub fn parse_input(input: &str) -> AnyResult<Vec<Blueprint>> {
-    let re_blueprint = Regex::new(r#"Blueprint (\d+)"#)?;
-    let re_robot = Regex::new(r#"Each (\w+) robot"#)?;
-    let re_costs = Regex::new(r#"(?:(\d+) (\w+))"#)?;
+    let src = Source::new(input);
+    let re_blueprint = Regex::new(r#"^Blueprint (\d+)$"#)?;
+    let re_robot = Regex::new(r#"^Each (\w+) robot costs (.+)$"#)?;
+    let re_cost = Regex::new(r#"^(\d+) (\w+)$"#)?;
+
+    let mineral = |s: &str| -> Result<Mineral, ParseError> { src.parse(s) };
 
     let mut blueprints = vec![];
     for line in input.lines() {
-        let (blueprint_str, robots_str) = line.split_once(':').ok_anyhow()?;
-        let match_blueprint = re_blueprint.captures(blueprint_str).ok_anyhow()?;
+        let (blueprint_str, robots_str) =
+            src.require(line.split_once(':'), line, "expected ':'")?;
+        let match_blueprint = src.require(
+            re_blueprint.captures(blueprint_str),
+            blueprint_str,
+            "expected 'Blueprint N'",
+        )?;
 
-        let id = match_blueprint.get(1).ok_anyhow()?.as_str().parse()?;
-        let mut blueprint = Blueprint { id, ..Default::default() };
+        let id = src.parse(match_blueprint.get(1).unwrap().as_str())?;
+        let mut blueprint = Blueprint {
+            id,
+            ..Default::default()
+        };
+        let mut robots_seen = [false; 4];
 
         for robot_spec_str in robots_str
             .split('.')
             .map(str::trim)
             .filter(|s| !s.is_empty())
         {
-            let (robot_str, costs_str) = robot_spec_str.split_once("costs").ok_anyhow()?;
-            let match_robot = re_robot.captures(robot_str).ok_anyhow()?;
+            let match_robot = src.require(
+                re_robot.captures(robot_spec_str),
+                robot_spec_str,
+                "expected 'Each <mineral> robot costs ...'",
+            )?;
 
             let mut cost = Cost::default();
-            for match_cost in re_costs.captures_iter(costs_str) {
-                let qty: i32 = match_cost.get(1).ok_anyhow()?.as_str().parse()?;
-                let res_type: Mineral = match_cost.get(2).ok_anyhow()?.as_str().parse()?;
+            for cost_str in match_robot.get(2).unwrap().as_str().split(" and ") {
+                let match_cost = src.require(
+                    re_cost.captures(cost_str),
+                    cost_str,
+                    "expected '<qty> <mineral>'",
+                )?;
+                let qty: i32 = src.parse(match_cost.get(1).unwrap().as_str())?;
+                let res_str = match_cost.get(2).unwrap().as_str();
 
-                match res_type {
+                match mineral(res_str)? {
                     Ore => cost.ore = qty,
                     Clay => cost.clay = qty,
                     Obsidian => cost.obsidian = qty,
-                    Geode => bail!("invalid resource type"),
+                    Geode => return Err(src.error(res_str, "robots cannot cost geodes").into()),
                 }
             }
 
-            let robot_type: Mineral = match_robot.get(1).ok_anyhow()?.as_str().parse()?;
+            let robot_str = match_robot.get(1).unwrap().as_str();
+            let robot_type = mineral(robot_str)?;
+            if std::mem::replace(&mut robots_seen[robot_type as usize], true) {
+                return Err(src.error(robot_str, "robot specified twice").into());
+            }
             match robot_type {
                 Ore => blueprint.ore_robot = cost,
                 Clay => blueprint.clay_robot = cost,
@@ -48,6 +74,11 @@ pub fn parse_input(input: &str) -> AnyResult<Vec<Blueprint>> {
             }
         }
 
+        if robots_seen.contains(&false) {
+            return Err(src
+                .error(line, "blueprint must specify all four robots")
+                .into());
+        }
         blueprints.push(blueprint);
     }
     Ok(blueprints)
@@ -66,4 +97,12 @@ mod tests {
             println!("{i:?}");
         }
     }
+
+    #[test]
+    fn unknown_mineral_located() {
+        let input = TEST_INPUT.replace("14 clay", "14 sand");
+        let err = parse_input(&input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 110, "sand"));
+    }
 }
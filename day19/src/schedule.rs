@@ -0,0 +1,175 @@
+//! The build schedule behind a search result, to see how it was reached.
+
+use std::fmt::Write;
+
+use rustc_hash::FxHashMap;
+
+use crate::economy::{Economy, State};
+
+/// The state at the end of every minute from the start, including the
+/// initial state, along the path the search found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub states: Vec<State>,
+}
+
+impl Schedule {
+    /// Follow the links back from `end` to the state with no predecessor.
+    pub(crate) fn retrace(came_from: &FxHashMap<State, State>, end: State) -> Self {
+        let mut states = vec![end];
+        while let Some(previous) = came_from.get(states.last().expect("never empty")) {
+            states.push(*previous);
+        }
+        states.reverse();
+        Schedule { states }
+    }
+
+    pub fn end(&self) -> State {
+        *self
+            .states
+            .last()
+            .expect("a schedule has at least the initial state")
+    }
+
+    /// The robot started in each minute, if any, from minute 1.
+    pub fn builds(&self) -> Vec<Option<usize>> {
+        self.states
+            .windows(2)
+            .map(|w| (0..w[1].robots.len()).find(|&r| w[1].robots[r] > w[0].robots[r]))
+            .collect()
+    }
+
+    /// Describe the schedule minute by minute, the way the puzzle does.
+    pub fn narrate(&self, economy: &Economy) -> String {
+        let robot = |r: usize| format!("{}-collecting robot", economy.names[r]);
+        let mut text = String::new();
+        for (minute, (w, build)) in self.states.windows(2).zip(self.builds()).enumerate() {
+            let (before, after) = (&w[0], &w[1]);
+            if minute > 0 {
+                text.push('\n');
+            }
+            writeln!(text, "== Minute {} ==", minute + 1).unwrap();
+
+            if let Some(r) = build {
+                let spent: Vec<_> = economy.costs[r]
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &qty)| qty > 0)
+                    .map(|(s, qty)| format!("{qty} {}", economy.names[s]))
+                    .collect();
+                let robot = robot(r);
+                let article = if robot.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    "an"
+                } else {
+                    "a"
+                };
+                writeln!(
+                    text,
+                    "Spend {} to start building {article} {robot}.",
+                    list(&spent)
+                )
+                .unwrap();
+            }
+
+            for r in 0..economy.len() {
+                let n = before.robots[r];
+                if n > 0 {
+                    let (plural, verb) = if n == 1 { ("", "s") } else { ("s", "") };
+                    let name = &economy.names[r];
+                    writeln!(
+                        text,
+                        "{n} {}{plural} collect{verb} {n} {name}; you now have {} {name}.",
+                        robot(r),
+                        after.resources[r]
+                    )
+                    .unwrap();
+                }
+            }
+
+            if let Some(r) = build {
+                writeln!(
+                    text,
+                    "The new {} is ready; you now have {} of them.",
+                    robot(r),
+                    after.robots[r]
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+}
+
+/// "a", "a and b", "a, b and c"
+fn list(items: &[String]) -> String {
+    match items {
+        [] => "nothing".to_owned(),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{} and {last}", init.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        economy::{optimise, try_advance_with, Objective, Spec},
+        parser::parse_input,
+        Mineral::*,
+        TEST_INPUT,
+    };
+    use indoc::indoc;
+
+    #[test]
+    fn schedule_replays() {
+        let blueprint = &parse_input(TEST_INPUT).unwrap()[0];
+        let spec = blueprint.to_spec(24);
+        let schedule = optimise(&spec).unwrap();
+        assert_eq!(schedule.states.len(), 25);
+        assert_eq!(schedule.end().resources[Geode as usize], 9);
+
+        let mut state = spec.initial_state();
+        for build in schedule.builds() {
+            state = match build {
+                Some(robot) => try_advance_with(&spec, &state, robot).unwrap(),
+                None => state.advance(),
+            };
+        }
+        assert_eq!(state, schedule.end());
+    }
+
+    #[test]
+    fn narrates_like_the_puzzle() {
+        let blueprint = &parse_input(TEST_INPUT).unwrap()[0];
+        let economy = blueprint.economy();
+        let spec = Spec::new(economy.clone(), 4, Objective::Maximise(Clay as usize)).unwrap();
+        let schedule = optimise(&spec).unwrap();
+        assert_eq!(schedule.builds(), [None, None, Some(Clay as usize), None]);
+
+        let expected = indoc! {"
+            == Minute 1 ==
+            1 ore-collecting robot collects 1 ore; you now have 1 ore.
+
+            == Minute 2 ==
+            1 ore-collecting robot collects 1 ore; you now have 2 ore.
+
+            == Minute 3 ==
+            Spend 2 ore to start building a clay-collecting robot.
+            1 ore-collecting robot collects 1 ore; you now have 1 ore.
+            The new clay-collecting robot is ready; you now have 1 of them.
+
+            == Minute 4 ==
+            1 ore-collecting robot collects 1 ore; you now have 2 ore.
+            1 clay-collecting robot collects 1 clay; you now have 1 clay.
+        "};
+        assert_eq!(schedule.narrate(&economy), expected);
+    }
+
+    #[test]
+    fn lists_costs() {
+        let items = ["3 ore", "14 clay", "2 sand"].map(String::from);
+        assert_eq!(list(&items[..1]), "3 ore");
+        assert_eq!(list(&items[..2]), "3 ore and 14 clay");
+        assert_eq!(list(&items), "3 ore, 14 clay and 2 sand");
+    }
+}
@@ -0,0 +1,226 @@
+use common::{
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+
+// this allows us to map a string to an enum value
+// e.g. "A" -> Opponent::A
+use strum::EnumString;
+
+/// Domain model: opponent's input, which
+/// gets mapped to rock, paper or scissors.
+#[derive(Copy, Clone, EnumString)]
+pub enum Opponent {
+    A, // rock
+    B, // paper
+    C, // scissors
+}
+
+/// Domain model: "my" input, X, Y or Z; this
+/// means different things depending on the part
+/// of the problem.
+#[derive(Copy, Clone, EnumString)]
+pub enum Myself {
+    X, // rock (part1); lose (part2)
+    Y, // paper (part1); draw (part2)
+    Z, // scissors (part1); win (part2)
+}
+
+/// Domain model: Rock, Paper or Scissors, with
+/// the value of the play as the enum value.
+#[derive(Copy, Clone)]
+enum Play {
+    R = 1,
+    P = 2,
+    S = 3,
+}
+
+// both parts - map opponent's input to a play
+impl From<Opponent> for Play {
+    fn from(opp: Opponent) -> Self {
+        match opp {
+            Opponent::A => Play::R,
+            Opponent::B => Play::P,
+            Opponent::C => Play::S,
+        }
+    }
+}
+
+// part 1 - map second column to a play
+impl From<Myself> for Play {
+    fn from(myself: Myself) -> Self {
+        match myself {
+            Myself::X => Play::R,
+            Myself::Y => Play::P,
+            Myself::Z => Play::S,
+        }
+    }
+}
+
+// part 2 - map second column to an outcome
+impl From<Myself> for Outcome {
+    fn from(myself: Myself) -> Self {
+        match myself {
+            Myself::X => Outcome::Lose,
+            Myself::Y => Outcome::Draw,
+            Myself::Z => Outcome::Win,
+        }
+    }
+}
+
+/// Domain model: result of the round, with
+/// associated value.
+#[derive(Copy, Clone)]
+enum Outcome {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+fn parse_round(src: Source, line: &str) -> Result<(Opponent, Myself), ParseError> {
+    let (opponent, myself) = src.require(line.split_once(' '), line, "expected two plays")?;
+    Ok((src.parse(opponent)?, src.parse(myself)?))
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<(Opponent, Myself)>> {
+    let src = Source::new(input);
+    let rounds = input
+        .lines()
+        .map(|line| parse_round(src, line))
+        .collect::<Result<_, _>>()?;
+    Ok(rounds)
+}
+
+mod part1 {
+    use crate::{Myself, Opponent, Outcome, Play};
+
+    fn outcome(opp: Opponent, me_play: Play) -> Outcome {
+        let opp_play: Play = opp.into();
+        match (me_play, opp_play) {
+            (Play::R, Play::R) => Outcome::Draw,
+            (Play::R, Play::P) => Outcome::Lose,
+            (Play::R, Play::S) => Outcome::Win,
+            (Play::P, Play::R) => Outcome::Win,
+            (Play::P, Play::P) => Outcome::Draw,
+            (Play::P, Play::S) => Outcome::Lose,
+            (Play::S, Play::R) => Outcome::Lose,
+            (Play::S, Play::P) => Outcome::Win,
+            (Play::S, Play::S) => Outcome::Draw,
+        }
+    }
+
+    fn score_round(opp: Opponent, me: Myself) -> i32 {
+        let my_play: Play = me.into();
+        let outcome = outcome(opp, my_play);
+        let outcome_value = outcome as i32;
+
+        let my_choice_value = my_play as i32;
+
+        outcome_value + my_choice_value
+    }
+
+    pub fn run_part1(rounds: &[(Opponent, Myself)]) -> i32 {
+        rounds.iter().map(|&(opp, me)| score_round(opp, me)).sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use indoc::indoc;
+
+        use crate::parse_input;
+        use crate::part1::score_round;
+        const EXPECTED_VALS: [i32; 3] = [8, 1, 6];
+        const TEST_INPUT: &str = indoc! {"
+            A Y
+            B X
+            C Z
+        "};
+
+        #[test]
+        fn scores_correct() {
+            let rounds = parse_input(TEST_INPUT).unwrap();
+
+            for (&(opp, me), expected) in rounds.iter().zip(EXPECTED_VALS) {
+                let score = score_round(opp, me);
+                assert_eq!(score, expected);
+            }
+        }
+    }
+}
+
+mod part2 {
+    use crate::{Myself, Opponent, Outcome, Play};
+
+    fn required_play(opp: Opponent, outcome: Outcome) -> Play {
+        let opp_play: Play = opp.into();
+        match (outcome, opp_play) {
+            (Outcome::Lose, Play::R) => Play::S,
+            (Outcome::Lose, Play::P) => Play::R,
+            (Outcome::Lose, Play::S) => Play::P,
+            (Outcome::Draw, Play::R) => Play::R,
+            (Outcome::Draw, Play::P) => Play::P,
+            (Outcome::Draw, Play::S) => Play::S,
+            (Outcome::Win, Play::R) => Play::P,
+            (Outcome::Win, Play::P) => Play::S,
+            (Outcome::Win, Play::S) => Play::R,
+        }
+    }
+
+    fn score_round(opp: Opponent, me: Myself) -> i32 {
+        let outcome: Outcome = me.into();
+        let my_play = required_play(opp, outcome);
+
+        let outcome_value = outcome as i32;
+        let my_choice_value = my_play as i32;
+        outcome_value + my_choice_value
+    }
+
+    pub fn run_part2(rounds: &[(Opponent, Myself)]) -> i32 {
+        rounds.iter().map(|&(opp, me)| score_round(opp, me)).sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::parse_input;
+        use crate::part2::score_round;
+        use indoc::indoc;
+
+        const EXPECTED_VALS: [i32; 3] = [4, 1, 7];
+        const TEST_INPUT: &str = indoc! {"
+            A Y
+            B X
+            C Z
+        "};
+
+        #[test]
+        fn scores_correct() {
+            let rounds = parse_input(TEST_INPUT).unwrap();
+
+            for (&(opp, me), expected) in rounds.iter().zip(EXPECTED_VALS) {
+                let score = score_round(opp, me);
+                assert_eq!(score, expected);
+            }
+        }
+    }
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+    type Input = Vec<(Opponent, Myself)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1::run_part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2::run_part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day2>(2, env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,228 @@
+pub mod order;
+
+use common::{parse::Source, AnyResult, Day, Solution};
+use itertools::Itertools;
+use order::OrderList;
+
+const PART2_KEY: i64 = 811589153;
+
+fn parse_input(input: &str) -> AnyResult<Vec<i64>> {
+    let src = Source::new(input);
+    let vals: Vec<i64> = input
+        .lines()
+        .map(|l| src.parse(l))
+        .collect::<Result<_, _>>()?;
+
+    // the grove coordinates are counted from the 0
+    if !vals.contains(&0) {
+        return Err(src.end_error("no 0 in the file").into());
+    }
+    Ok(vals)
+}
+
+fn positions_for<T>(moves: &[T]) -> OrderList {
+    OrderList::new(moves.len())
+}
+
+fn calculate_permutations(positions: &mut OrderList, moves: &[i64]) {
+    let len = moves.len();
+    if len < 2 {
+        return;
+    }
+    for (curr_pos, mv) in moves.iter().enumerate() {
+        // skip no-move cases
+        if *mv == 0 {
+            continue;
+        }
+
+        // the element moves around the circle formed by all the others
+        let curr_idx = positions.remove(curr_pos);
+        let insert_idx = (curr_idx as i64 + *mv).rem_euclid(len as i64 - 1) as usize;
+        positions.insert(curr_pos, insert_idx);
+    }
+}
+
+fn permute(array: &[i64], positions: &OrderList) -> Vec<i64> {
+    positions.to_vec().iter().map(|ix| array[*ix]).collect()
+}
+
+fn calculate_result(mixed: Vec<i64>) -> i64 {
+    // note: position of zero is the start; it doesn't matter what overall
+    // rotation the array has, as we're starting the cycle here.
+    let pos_zero = mixed.iter().position(|a| *a == 0).unwrap();
+    let mut sum = 0;
+    for idx in [1000, 2000, 3000] {
+        let val = mixed.iter().cycle().skip(pos_zero).nth(idx).unwrap();
+        sum += val;
+    }
+    sum
+}
+
+fn part1(array: &[i64]) -> i64 {
+    let mut positions = positions_for(array);
+    calculate_permutations(&mut positions, array);
+    let mixed = permute(array, &positions);
+
+    calculate_result(mixed)
+}
+
+fn part2(array: &[i64]) -> i64 {
+    let moves = array.iter().map(|v| v * PART2_KEY).collect_vec();
+
+    let mut positions = positions_for(&moves);
+    for _ in 1..=10 {
+        calculate_permutations(&mut positions, &moves);
+    }
+
+    let mixed = permute(&moves, &positions);
+
+    calculate_result(mixed)
+}
+
+pub struct Day20;
+impl Solution for Day20 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day20>(20, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        1
+        2
+        -3
+        3
+        -2
+        0
+        4
+    "};
+
+    // Assert that arrays are equal for any rotation of the right
+    // array; i.e. the elements need to be in the correct (cyclical) order,
+    // but not necessarily starting at the same location.
+    fn assert_eq_rotate<T>(left: &[T], right: &[T])
+    where
+        T: PartialEq + std::fmt::Debug + Clone,
+    {
+        if !check_eq_rotate(left, right) {
+            assert_eq!(left, right);
+        }
+    }
+
+    fn check_eq_rotate<T>(left: &[T], right: &[T]) -> bool
+    where
+        T: PartialEq + std::fmt::Debug + Clone,
+    {
+        let mut right = right.iter().cloned().collect_vec();
+        for _ in 0..left.len() {
+            if left == right.as_slice() {
+                return true;
+            }
+            right.rotate_left(1);
+        }
+        false
+    }
+
+    // the original mixing, shuffling a Vec
+    fn mix_by_shuffling(moves: &[i64]) -> Vec<i64> {
+        let len = moves.len() as i64;
+        let mut positions: Vec<usize> = (0..moves.len()).collect();
+        for (curr_pos, mv) in moves.iter().enumerate() {
+            let curr_idx = positions.iter().position(|p| *p == curr_pos).unwrap();
+            positions.remove(curr_idx);
+            let insert_idx = (curr_idx as i64 + mv).rem_euclid(len - 1) as usize;
+            positions.insert(insert_idx, curr_pos);
+        }
+        positions.iter().map(|ix| moves[*ix]).collect()
+    }
+
+    fn pseudo_random_moves(len: usize) -> Vec<i64> {
+        let mut seed = 7_i64;
+        (0..len)
+            .map(|_| {
+                seed = (seed * 48271) % 0x7fff_ffff;
+                (seed % 20_001 - 10_000) * PART2_KEY
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_input_correct() {
+        parse_input(TEST_INPUT).unwrap();
+    }
+
+    #[test]
+    fn calculate_permutations_fast_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let mut positions = positions_for(&input);
+        calculate_permutations(&mut positions, &input);
+        let res = permute(&input, &positions);
+        assert_eq_rotate(&res, &[1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    #[test]
+    fn mixing_matches_shuffling() {
+        let moves = pseudo_random_moves(3000);
+        let mut positions = positions_for(&moves);
+        calculate_permutations(&mut positions, &moves);
+        assert_eq!(permute(&moves, &positions), mix_by_shuffling(&moves));
+    }
+
+    #[test]
+    fn part1_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&input);
+        assert_eq!(res, 3);
+    }
+
+    #[test]
+    fn part2_mix_correct() {
+        let mut moves = parse_input(TEST_INPUT).unwrap();
+        moves.iter_mut().for_each(|v| *v *= PART2_KEY);
+        let mut positions = positions_for(&moves);
+        for i in 1..=10 {
+            calculate_permutations(&mut positions, &moves);
+            let permuted = permute(&moves, &positions);
+            println!("iteration {i} => {permuted:?}");
+        }
+
+        let permuted = permute(&moves, &positions);
+        assert_eq_rotate(
+            &permuted,
+            &[
+                0,
+                -2434767459,
+                1623178306,
+                3246356612,
+                -1623178306,
+                2434767459,
+                811589153,
+            ],
+        );
+    }
+
+    #[test]
+    fn part2_correct() {
+        let moves = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&moves);
+        assert_eq!(res, 1623178306);
+    }
+}
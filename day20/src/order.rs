@@ -0,0 +1,212 @@
+//! A list of the numbers `0..n` in some order, which can find where any
+//! number is and move it elsewhere in logarithmic time.
+//!
+//! It's an implicit treap: a binary tree in list order, kept balanced by
+//! random heap priorities, where each node knows the size of its subtree.
+//! Node `i` holds the number `i`, and parent links let a number's index be
+//! counted up from its node.
+
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Node {
+    left: usize,
+    right: usize,
+    parent: usize,
+    size: usize,
+    priority: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderList {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+impl OrderList {
+    /// The numbers `0..len` in order.
+    pub fn new(len: usize) -> Self {
+        // xorshift is plenty random enough to balance the tree
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let nodes = (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                Node {
+                    left: NIL,
+                    right: NIL,
+                    parent: NIL,
+                    size: 1,
+                    priority: state,
+                }
+            })
+            .collect();
+
+        let mut list = OrderList { nodes, root: NIL };
+        for node in 0..len {
+            list.root = list.merge(list.root, node);
+        }
+        list.detach(list.root);
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == NIL
+    }
+
+    /// Where `number` is in the list.
+    pub fn index_of(&self, number: usize) -> usize {
+        let mut node = number;
+        let mut index = self.size(self.nodes[node].left);
+        while self.nodes[node].parent != NIL {
+            let parent = self.nodes[node].parent;
+            if self.nodes[parent].right == node {
+                index += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+        index
+    }
+
+    /// Take `number` out of the list, returning the index it was at.
+    pub fn remove(&mut self, number: usize) -> usize {
+        let index = self.index_of(number);
+        let (before, rest) = self.split(self.root, index);
+        let (taken, after) = self.split(rest, 1);
+        debug_assert_eq!(taken, number);
+        self.detach(taken);
+        self.root = self.merge(before, after);
+        self.detach(self.root);
+        index
+    }
+
+    /// Put `number`, which must have been removed, back in at `index`.
+    pub fn insert(&mut self, number: usize, index: usize) {
+        let (before, after) = self.split(self.root, index);
+        let joined = self.merge(before, number);
+        self.root = self.merge(joined, after);
+        self.detach(self.root);
+    }
+
+    /// The numbers, in list order.
+    pub fn to_vec(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![];
+        let mut node = self.root;
+        while node != NIL || !stack.is_empty() {
+            while node != NIL {
+                stack.push(node);
+                node = self.nodes[node].left;
+            }
+            let next = stack.pop().expect("stack is not empty");
+            order.push(next);
+            node = self.nodes[next].right;
+        }
+        order
+    }
+
+    fn size(&self, node: usize) -> usize {
+        if node == NIL {
+            0
+        } else {
+            self.nodes[node].size
+        }
+    }
+
+    fn detach(&mut self, node: usize) {
+        if node != NIL {
+            self.nodes[node].parent = NIL;
+        }
+    }
+
+    fn set_children(&mut self, node: usize, left: usize, right: usize) {
+        for child in [left, right] {
+            if child != NIL {
+                self.nodes[child].parent = node;
+            }
+        }
+        let size = self.size(left) + self.size(right) + 1;
+        let n = &mut self.nodes[node];
+        (n.left, n.right, n.size) = (left, right, size);
+    }
+
+    /// Join two trees, with all of `a` before all of `b`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            let right = self.merge(self.nodes[a].right, b);
+            self.set_children(a, self.nodes[a].left, right);
+            a
+        } else {
+            let left = self.merge(a, self.nodes[b].left);
+            self.set_children(b, left, self.nodes[b].right);
+            b
+        }
+    }
+
+    /// Split a tree into its first `count` numbers and the rest. The roots
+    /// returned may still have stale parent links.
+    fn split(&mut self, node: usize, count: usize) -> (usize, usize) {
+        if node == NIL {
+            return (NIL, NIL);
+        }
+        let Node { left, right, .. } = self.nodes[node];
+        if self.size(left) >= count {
+            let (before, after) = self.split(left, count);
+            self.set_children(node, after, right);
+            (before, node)
+        } else {
+            let (before, after) = self.split(right, count - self.size(left) - 1);
+            self.set_children(node, left, before);
+            (node, after)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_in_order() {
+        let list = OrderList::new(100);
+        assert_eq!(list.to_vec(), (0..100).collect::<Vec<_>>());
+        assert!((0..100).all(|n| list.index_of(n) == n));
+        assert!(OrderList::new(0).is_empty());
+    }
+
+    #[test]
+    fn moves_like_a_vec() {
+        let len = 500;
+        let mut list = OrderList::new(len);
+        let mut expected: Vec<usize> = (0..len).collect();
+        let mut seed = 12345_usize;
+        for _ in 0..2000 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let number = (seed >> 33) % len;
+            let to = (seed >> 13) % len;
+
+            let from = expected.iter().position(|&n| n == number).unwrap();
+            assert_eq!(list.index_of(number), from);
+            expected.remove(from);
+            expected.insert(to, number);
+
+            assert_eq!(list.remove(number), from);
+            list.insert(number, to);
+        }
+        assert_eq!(list.to_vec(), expected);
+        assert_eq!(list.len(), len);
+    }
+}
@@ -0,0 +1,276 @@
+//! The monkeys' programs as expression trees, for reading rather than
+//! evaluating: printed as formulas, with the constant parts worked out,
+//! or drawn as a Graphviz graph of which monkey listens to which.
+
+use std::{collections::HashMap, fmt::Display, fmt::Write};
+
+use anyhow::{anyhow, bail};
+use common::AnyResult;
+
+use crate::{rational::Rational, MonkeyExpr, Monkeys, Op};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(Rational),
+    Variable(String),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Op {
+    fn symbol(&self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Op::Add | Op::Sub => 1,
+            Op::Mul | Op::Div => 2,
+        }
+    }
+}
+
+impl Expr {
+    /// The formula for the number monkey `id` yells. The monkey named
+    /// `variable`, if any, is left as its name instead of its number.
+    pub fn from_monkey(monkeys: &Monkeys, id: &str, variable: Option<&str>) -> AnyResult<Expr> {
+        fn build<'a>(
+            monkeys: &'a Monkeys,
+            id: &'a str,
+            variable: Option<&str>,
+            path: &mut Vec<&'a str>,
+        ) -> AnyResult<Expr> {
+            if variable == Some(id) {
+                return Ok(Expr::Variable(id.to_owned()));
+            }
+            if path.contains(&id) {
+                bail!("monkey {id} depends on its own number");
+            }
+            let expr = monkeys
+                .get(id)
+                .ok_or_else(|| anyhow!("no such monkey {id}"))?;
+            Ok(match expr {
+                MonkeyExpr::Literal(v) => Expr::Number(Rational::integer(*v)),
+                MonkeyExpr::Operation(op, l, r) => {
+                    path.push(id);
+                    let left = build(monkeys, l, variable, path)?;
+                    let right = build(monkeys, r, variable, path)?;
+                    path.pop();
+                    Expr::Binary(*op, Box::new(left), Box::new(right))
+                }
+            })
+        }
+        build(monkeys, id, variable, &mut vec![])
+    }
+
+    /// Work out every part of the formula that doesn't involve a variable.
+    pub fn fold_constants(self) -> AnyResult<Expr> {
+        let Expr::Binary(op, left, right) = self else {
+            return Ok(self);
+        };
+        Ok(match (left.fold_constants()?, right.fold_constants()?) {
+            (Expr::Number(l), Expr::Number(r)) => Expr::Number(match op {
+                Op::Add => l.checked_add(r)?,
+                Op::Sub => l.checked_sub(r)?,
+                Op::Mul => l.checked_mul(r)?,
+                Op::Div => l.checked_div(r)?,
+            }),
+            (left, right) => Expr::Binary(op, Box::new(left), Box::new(right)),
+        })
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(op, _, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Number(n) if n.to_integer().is_some() => write!(f, "{n}"),
+            // a fraction is itself a division, so needs its own brackets
+            Expr::Number(n) => write!(f, "({n})"),
+            Expr::Variable(name) => f.write_str(name),
+            Expr::Binary(op, left, right) => {
+                // only brackets that change the meaning are needed: around
+                // looser operations, and on the right of '-' and '/'
+                let left_brackets = left.precedence() < op.precedence();
+                let right_brackets = right.precedence() < op.precedence()
+                    || (right.precedence() == op.precedence() && matches!(op, Op::Sub | Op::Div));
+                write_operand(f, left, left_brackets)?;
+                write!(f, " {} ", op.symbol())?;
+                write_operand(f, right, right_brackets)
+            }
+        }
+    }
+}
+
+fn write_operand(f: &mut std::fmt::Formatter<'_>, expr: &Expr, brackets: bool) -> std::fmt::Result {
+    if brackets {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+/// Draw the monkeys `root` depends on as a Graphviz DOT graph, with an edge
+/// from each monkey to the ones it listens to. The monkey named `variable`,
+/// and every monkey whose number depends on it, are highlighted.
+pub fn to_dot(monkeys: &Monkeys, root: &str, variable: Option<&str>) -> AnyResult<String> {
+    // monkeys in the order they're first reached from the root
+    let mut order = vec![];
+    let mut depends: HashMap<&str, bool> = HashMap::new();
+    fn visit<'a>(
+        monkeys: &'a Monkeys,
+        id: &'a str,
+        variable: Option<&str>,
+        order: &mut Vec<&'a str>,
+        depends: &mut HashMap<&'a str, bool>,
+    ) -> AnyResult<bool> {
+        if let Some(&d) = depends.get(id) {
+            return Ok(d);
+        }
+        let expr = monkeys
+            .get(id)
+            .ok_or_else(|| anyhow!("no such monkey {id}"))?;
+        order.push(id);
+        // provisionally, so that a loop ends here rather than recursing forever
+        depends.insert(id, variable == Some(id));
+        let mut d = variable == Some(id);
+        if let MonkeyExpr::Operation(_, l, r) = expr {
+            d |= visit(monkeys, l, variable, order, depends)?;
+            d |= visit(monkeys, r, variable, order, depends)?;
+        }
+        depends.insert(id, d);
+        Ok(d)
+    }
+    visit(monkeys, root, variable, &mut order, &mut depends)?;
+
+    let mut dot = String::from("digraph monkeys {\n");
+    for id in &order {
+        let label = match &monkeys[*id] {
+            MonkeyExpr::Literal(v) => format!("{id}\\n{v}"),
+            MonkeyExpr::Operation(op, _, _) => format!("{id}\\n{}", op.symbol()),
+        };
+        let style = if variable == Some(*id) {
+            ", shape=box, style=filled, fillcolor=gold"
+        } else if depends[*id] {
+            ", style=filled, fillcolor=lightpink"
+        } else {
+            ""
+        };
+        writeln!(dot, "    \"{id}\" [label=\"{label}\"{style}];")?;
+    }
+    for id in &order {
+        if let MonkeyExpr::Operation(_, l, r) = &monkeys[*id] {
+            writeln!(dot, "    \"{id}\" -> \"{l}\";")?;
+            writeln!(dot, "    \"{id}\" -> \"{r}\";")?;
+        }
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, tests::TEST_INPUT};
+
+    fn formula(id: &str, variable: Option<&str>) -> Expr {
+        let monkeys = parse_input(TEST_INPUT).unwrap();
+        Expr::from_monkey(&monkeys, id, variable).unwrap()
+    }
+
+    #[test]
+    fn prints_formulas() {
+        assert_eq!(
+            formula("root", None).to_string(),
+            "(4 + 2 * (5 - 3)) / 4 + (32 - 2) * 5"
+        );
+        assert_eq!(
+            formula("root", Some("humn")).to_string(),
+            "(4 + 2 * (humn - 3)) / 4 + (32 - 2) * 5"
+        );
+    }
+
+    #[test]
+    fn minimal_brackets() {
+        let var = |name: &str| Box::new(Expr::Variable(name.to_owned()));
+        let bin = |op, l, r| Box::new(Expr::Binary(op, l, r));
+        let cases = [
+            (
+                bin(Op::Sub, bin(Op::Sub, var("a"), var("b")), var("c")),
+                "a - b - c",
+            ),
+            (
+                bin(Op::Sub, var("a"), bin(Op::Sub, var("b"), var("c"))),
+                "a - (b - c)",
+            ),
+            (
+                bin(Op::Add, var("a"), bin(Op::Sub, var("b"), var("c"))),
+                "a + b - c",
+            ),
+            (
+                bin(Op::Div, var("a"), bin(Op::Mul, var("b"), var("c"))),
+                "a / (b * c)",
+            ),
+            (
+                bin(Op::Mul, bin(Op::Add, var("a"), var("b")), var("c")),
+                "(a + b) * c",
+            ),
+            (
+                bin(Op::Add, var("a"), bin(Op::Mul, var("b"), var("c"))),
+                "a + b * c",
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(expr.to_string(), expected);
+        }
+        let half = Expr::Number(Rational::new(1, 2).unwrap());
+        assert_eq!(
+            bin(Op::Div, var("a"), Box::new(half)).to_string(),
+            "a / (1/2)"
+        );
+    }
+
+    #[test]
+    fn folds_constants() {
+        let folded = formula("root", Some("humn")).fold_constants().unwrap();
+        assert_eq!(folded.to_string(), "(4 + 2 * (humn - 3)) / 4 + 150");
+        let folded = formula("root", None).fold_constants().unwrap();
+        assert_eq!(folded, Expr::Number(Rational::integer(152)));
+    }
+
+    #[test]
+    fn loops_reported() {
+        let looped = TEST_INPUT.replace("hmdt: 32", "hmdt: drzm + zczc");
+        let monkeys = parse_input(&looped).unwrap();
+        assert!(Expr::from_monkey(&monkeys, "root", None).is_err());
+    }
+
+    #[test]
+    fn exports_dot() {
+        let monkeys = parse_input(TEST_INPUT).unwrap();
+        let dot = to_dot(&monkeys, "pppw", Some("humn")).unwrap();
+        let lines: Vec<_> = dot.lines().collect();
+        assert_eq!(lines[0], "digraph monkeys {");
+        assert_eq!(
+            lines[1],
+            r#"    "pppw" [label="pppw\n/", style=filled, fillcolor=lightpink];"#
+        );
+        assert!(lines.contains(
+            &r#"    "humn" [label="humn\n5", shape=box, style=filled, fillcolor=gold];"#
+        ));
+        assert!(lines.contains(&r#"    "lfqf" [label="lfqf\n4"];"#));
+        assert!(lines.contains(&r#"    "lgvd" -> "ptdq";"#));
+        // the monkeys pppw depends on, plus the edges between them
+        assert_eq!(lines.len(), 2 + 9 + 8);
+    }
+}
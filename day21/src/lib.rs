@@ -0,0 +1,184 @@
+pub mod expr;
+pub mod linear;
+pub mod rational;
+
+use std::{collections::HashMap, str::FromStr};
+
+use common::{parse::Source, AnyResult, Day, Solution};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+impl FromStr for Op {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Op::Add),
+            "-" => Ok(Op::Sub),
+            "*" => Ok(Op::Mul),
+            "/" => Ok(Op::Div),
+            _ => Err(anyhow::anyhow!("Invalid operation: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MonkeyExpr {
+    Literal(i64),
+    Operation(Op, String, String),
+}
+
+pub type Monkeys = HashMap<String, MonkeyExpr>;
+
+fn parse_input(input: &str) -> AnyResult<Monkeys> {
+    let src = Source::new(input);
+    let re_literal = Regex::new(r#"^(\w+): (\d+)$"#)?;
+    let re_expr = Regex::new(r#"^(\w+): (\w+) (\S) (\w+)$"#)?;
+
+    let mut monkeys = HashMap::new();
+    let mut references = vec![];
+    for line in input.lines() {
+        let (id, expr) = if let Some(literal) = re_literal.captures(line) {
+            let val = src.parse(literal.get(2).unwrap().as_str())?;
+            (literal.get(1).unwrap(), MonkeyExpr::Literal(val))
+        } else if let Some(expression) = re_expr.captures(line) {
+            let left = expression.get(2).unwrap().as_str();
+            let op = src.parse(expression.get(3).unwrap().as_str())?;
+            let right = expression.get(4).unwrap().as_str();
+            references.extend([left, right]);
+            (
+                expression.get(1).unwrap(),
+                MonkeyExpr::Operation(op, left.to_owned(), right.to_owned()),
+            )
+        } else {
+            return Err(src
+                .error(line, "expected 'name: number' or 'name: name op name'")
+                .into());
+        };
+
+        if monkeys.insert(id.as_str().to_owned(), expr).is_some() {
+            return Err(src.error(id.as_str(), "monkey defined twice").into());
+        }
+    }
+
+    for name in references {
+        if !monkeys.contains_key(name) {
+            return Err(src.error(name, "no such monkey").into());
+        }
+    }
+    if !monkeys.contains_key("root") {
+        return Err(src.end_error("missing the 'root' monkey").into());
+    }
+
+    Ok(monkeys)
+}
+
+/// What `root` yells, rounded to the nearest integer as it always has been
+/// when a division along the way doesn't come out whole.
+fn part1(monkeys: &Monkeys) -> AnyResult<i64> {
+    let root = linear::reduce(monkeys, "root", None)?.constant;
+    root.round()
+        .ok_or_else(|| anyhow::anyhow!("root yells {root}, which is too big"))
+}
+
+/// Determine the value for `humn` such that the root node's left and right
+/// operands are equal, by reducing both to linear expressions in `humn`.
+fn part2(monkeys: &Monkeys) -> AnyResult<i64> {
+    let humn = linear::solve_root(monkeys, "humn")?;
+    humn.to_integer().ok_or_else(|| {
+        anyhow::anyhow!("humn would have to yell {humn}, which isn't a whole number")
+    })
+}
+
+pub struct Day21;
+impl Solution for Day21 {
+    type Input = Monkeys;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input)
+    }
+}
+
+pub const DAY: Day = Day::new::<Day21>(21, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    pub(crate) const TEST_INPUT: &str = indoc! {"
+        root: pppw + sjmn
+        dbpl: 5
+        cczh: sllz + lgvd
+        zczc: 2
+        ptdq: humn - dvpt
+        dvpt: 3
+        lfqf: 4
+        humn: 5
+        ljgn: 2
+        sjmn: drzm * dbpl
+        sllz: 4
+        pppw: cczh / lfqf
+        lgvd: ljgn * ptdq
+        drzm: hmdt - zczc
+        hmdt: 32
+    "};
+
+    #[test]
+    fn parse_input_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        for i in &input {
+            println!("{i:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_monkeys_located() {
+        let locate = |input: &str| {
+            let err = parse_input(input).unwrap_err();
+            let err = err.downcast::<common::parse::ParseError>().unwrap();
+            (err.line, err.column, err.text)
+        };
+        let bad_op = TEST_INPUT.replace("drzm * dbpl", "drzm % dbpl");
+        assert_eq!(locate(&bad_op), (10, 12, "%".to_owned()));
+        let unknown = TEST_INPUT.replace("ljgn * ptdq", "ljgn * ptdx");
+        assert_eq!(locate(&unknown), (13, 14, "ptdx".to_owned()));
+    }
+
+    #[test]
+    fn part1_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&input).unwrap();
+        assert_eq!(res, 152);
+    }
+
+    #[test]
+    fn part1_rounds_inexact_division() {
+        // pppw is now 8 / 3, leaving root at 150 + 8 / 3
+        let input = parse_input(&TEST_INPUT.replace("lfqf: 4", "lfqf: 3")).unwrap();
+        assert_eq!(part1(&input).unwrap(), 153);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&input).unwrap();
+        assert_eq!(res, 301);
+    }
+}
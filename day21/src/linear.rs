@@ -0,0 +1,183 @@
+//! Working out monkeys' numbers exactly, with one monkey left as an unknown.
+//!
+//! Every number is kept as a linear expression `a·x + b` in the unknown
+//! `x`, over exact fractions. That's enough for the monkeys' programs as
+//! long as no monkey multiplies the unknown by itself or divides by it,
+//! and makes finding the unknown a matter of rearranging one equation.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context};
+use common::AnyResult;
+
+use crate::{rational::Rational, MonkeyExpr, Monkeys, Op};
+
+/// `coeff·x + constant`, for the unknown `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear {
+    pub coeff: Rational,
+    pub constant: Rational,
+}
+
+impl Linear {
+    const UNKNOWN: Linear = Linear {
+        coeff: Rational::ONE,
+        constant: Rational::ZERO,
+    };
+
+    pub fn constant(value: Rational) -> Self {
+        Linear {
+            coeff: Rational::ZERO,
+            constant: value,
+        }
+    }
+
+    pub fn depends_on_unknown(&self) -> bool {
+        !self.coeff.is_zero()
+    }
+
+    fn apply(self, op: Op, other: Linear) -> AnyResult<Linear> {
+        Ok(match op {
+            Op::Add => Linear {
+                coeff: self.coeff.checked_add(other.coeff)?,
+                constant: self.constant.checked_add(other.constant)?,
+            },
+            Op::Sub => Linear {
+                coeff: self.coeff.checked_sub(other.coeff)?,
+                constant: self.constant.checked_sub(other.constant)?,
+            },
+            Op::Mul => {
+                let (scaled, by) = match (self.depends_on_unknown(), other.depends_on_unknown()) {
+                    (true, true) => bail!("both sides of '*' depend on the unknown"),
+                    (true, false) => (self, other.constant),
+                    (false, _) => (other, self.constant),
+                };
+                Linear {
+                    coeff: scaled.coeff.checked_mul(by)?,
+                    constant: scaled.constant.checked_mul(by)?,
+                }
+            }
+            Op::Div => {
+                if other.depends_on_unknown() {
+                    bail!("divides by an expression that depends on the unknown");
+                }
+                Linear {
+                    coeff: self.coeff.checked_div(other.constant)?,
+                    constant: self.constant.checked_div(other.constant)?,
+                }
+            }
+        })
+    }
+}
+
+/// Reduce the number `id` yells to a linear expression in the monkey named
+/// `unknown`, or to a constant if there is no unknown.
+pub fn reduce(monkeys: &Monkeys, id: &str, unknown: Option<&str>) -> AnyResult<Linear> {
+    Reducer {
+        monkeys,
+        unknown,
+        done: HashMap::new(),
+    }
+    .reduce(id)
+}
+
+struct Reducer<'a> {
+    monkeys: &'a Monkeys,
+    unknown: Option<&'a str>,
+    // None while a monkey is being worked out, to catch loops
+    done: HashMap<&'a str, Option<Linear>>,
+}
+
+impl<'a> Reducer<'a> {
+    fn reduce(&mut self, id: &'a str) -> AnyResult<Linear> {
+        if self.unknown == Some(id) {
+            return Ok(Linear::UNKNOWN);
+        }
+        match self.done.get(id) {
+            Some(Some(value)) => return Ok(*value),
+            Some(None) => bail!("monkey {id} depends on its own number"),
+            None => {}
+        }
+
+        self.done.insert(id, None);
+        let expr = self
+            .monkeys
+            .get(id)
+            .ok_or_else(|| anyhow!("no such monkey {id}"))?;
+        let value = match expr {
+            MonkeyExpr::Literal(v) => Linear::constant(Rational::integer(*v)),
+            MonkeyExpr::Operation(op, l, r) => {
+                let left = self.reduce(l)?;
+                let right = self.reduce(r)?;
+                left.apply(*op, right)
+                    .with_context(|| format!("monkey {id} can't be reduced"))?
+            }
+        };
+        self.done.insert(id, Some(value));
+        Ok(value)
+    }
+}
+
+/// Find the number `unknown` must yell for both sides of `root`'s
+/// operation to be equal.
+pub fn solve_root(monkeys: &Monkeys, unknown: &str) -> AnyResult<Rational> {
+    let Some(MonkeyExpr::Operation(_, l, r)) = monkeys.get("root") else {
+        bail!("root must compare the numbers of two monkeys");
+    };
+    let left = reduce(monkeys, l, Some(unknown))?;
+    let right = reduce(monkeys, r, Some(unknown))?;
+
+    // a·x + b = c·x + d, so x = (d - b) / (a - c)
+    let coeff = left.coeff.checked_sub(right.coeff)?;
+    if coeff.is_zero() {
+        bail!("root's operands don't depend on {unknown}, so it can't be solved for");
+    }
+    right
+        .constant
+        .checked_sub(left.constant)?
+        .checked_div(coeff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, tests::TEST_INPUT};
+
+    #[test]
+    fn reduces_test_input() {
+        let monkeys = parse_input(TEST_INPUT).unwrap();
+        // pppw = (4 + 2 * (x - 3)) / 4
+        let pppw = reduce(&monkeys, "pppw", Some("humn")).unwrap();
+        assert_eq!(pppw.coeff, Rational::new(1, 2).unwrap());
+        assert_eq!(pppw.constant, Rational::new(-1, 2).unwrap());
+        assert_eq!(
+            reduce(&monkeys, "sjmn", Some("humn")).unwrap(),
+            Linear::constant(Rational::integer(150))
+        );
+        assert_eq!(
+            solve_root(&monkeys, "humn").unwrap(),
+            Rational::integer(301)
+        );
+    }
+
+    #[test]
+    fn non_linear_reported() {
+        let squared = TEST_INPUT.replace("ljgn * ptdq", "ptdq * ptdq");
+        let monkeys = parse_input(&squared).unwrap();
+        let err = solve_root(&monkeys, "humn").unwrap_err();
+        assert_eq!(err.to_string(), "monkey lgvd can't be reduced");
+        assert!(format!("{err:#}").contains("both sides of '*'"));
+
+        let divided = TEST_INPUT.replace("cczh / lfqf", "lfqf / cczh");
+        let monkeys = parse_input(&divided).unwrap();
+        let err = solve_root(&monkeys, "humn").unwrap_err();
+        assert!(format!("{err:#}").contains("divides by an expression"));
+    }
+
+    #[test]
+    fn loops_reported() {
+        let looped = TEST_INPUT.replace("hmdt: 32", "hmdt: drzm + zczc");
+        let monkeys = parse_input(&looped).unwrap();
+        assert!(reduce(&monkeys, "root", None).is_err());
+    }
+}
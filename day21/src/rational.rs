@@ -0,0 +1,161 @@
+//! Exact fractions, so dividing never loses anything.
+
+use std::{fmt::Display, ops::Neg};
+
+use anyhow::{anyhow, bail};
+use common::AnyResult;
+
+/// A fraction in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> AnyResult<Self> {
+        if den == 0 {
+            bail!("division by zero");
+        }
+        let g = gcd(num, den);
+        let sign = den.signum();
+        Ok(Rational {
+            num: sign * num / g,
+            den: sign * den / g,
+        })
+    }
+
+    pub fn integer(n: i64) -> Self {
+        Rational {
+            num: n as i128,
+            den: 1,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is a whole number that fits.
+    pub fn to_integer(self) -> Option<i64> {
+        (self.den == 1).then(|| self.num.try_into().ok()).flatten()
+    }
+
+    /// The nearest integer, with halves rounded away from zero as
+    /// [`f64::round`] does, if it fits.
+    pub fn round(self) -> Option<i64> {
+        let half_up = (2 * self.num.abs() + self.den) / (2 * self.den);
+        (self.num.signum() * half_up).try_into().ok()
+    }
+
+    pub fn checked_add(self, other: Self) -> AnyResult<Self> {
+        let num = checked(self.num.checked_mul(other.den))?
+            .checked_add(checked(other.num.checked_mul(self.den))?);
+        Rational::new(checked(num)?, checked(self.den.checked_mul(other.den))?)
+    }
+
+    pub fn checked_sub(self, other: Self) -> AnyResult<Self> {
+        self.checked_add(other.neg())
+    }
+
+    pub fn checked_mul(self, other: Self) -> AnyResult<Self> {
+        // cancel first, to keep the products small
+        let a = gcd(self.num, other.den);
+        let b = gcd(other.num, self.den);
+        Rational::new(
+            checked((self.num / a).checked_mul(other.num / b))?,
+            checked((self.den / b).checked_mul(other.den / a))?,
+        )
+    }
+
+    pub fn checked_div(self, other: Self) -> AnyResult<Self> {
+        if other.is_zero() {
+            bail!("division by zero");
+        }
+        self.checked_mul(Rational {
+            num: other.den * other.num.signum(),
+            den: other.num.abs(),
+        })
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+fn checked(value: Option<i128>) -> AnyResult<i128> {
+    value.ok_or_else(|| anyhow!("arithmetic overflow"))
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn normalised() {
+        assert_eq!(r(4, -6), r(-2, 3));
+        assert_eq!(r(0, 5), Rational::ZERO);
+        assert_eq!(r(-2, 3).to_string(), "-2/3");
+        assert!(Rational::new(1, 0).is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(r(1, 2).checked_add(r(1, 3)).unwrap(), r(5, 6));
+        assert_eq!(r(1, 2).checked_sub(r(1, 3)).unwrap(), r(1, 6));
+        assert_eq!(r(2, 3).checked_mul(r(9, 4)).unwrap(), r(3, 2));
+        assert_eq!(r(2, 3).checked_div(r(-4, 9)).unwrap(), r(-3, 2));
+        assert!(r(1, 2).checked_div(Rational::ZERO).is_err());
+        assert_eq!(r(6, 3).to_integer(), Some(2));
+        assert_eq!(r(1, 3).to_integer(), None);
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(r(8, 3).round(), Some(3));
+        assert_eq!(r(7, 3).round(), Some(2));
+        assert_eq!(r(5, 2).round(), Some(3));
+        assert_eq!(r(-5, 2).round(), Some(-3));
+        assert_eq!(r(-7, 3).round(), Some(-2));
+        assert_eq!(Rational::ZERO.round(), Some(0));
+    }
+
+    #[test]
+    fn overflow_reported() {
+        let big = Rational::integer(i64::MAX);
+        let huge = big.checked_mul(big).unwrap();
+        assert!(huge.checked_mul(big).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Folding the flat map into a cube, to work out how the faces join up.
+//!
+//! Each face is given an orientation in 3D by rolling the cube across the
+//! net from the first face. Two faces then share an edge when one lies in
+//! the direction the other's edge points, which gives the [`Topology`]
+//! for any of the 11 nets of a cube.
+
+use std::collections::HashMap;
+
+use anyhow::bail;
+use common::*;
+
+use crate::{
+    part2::{Connection, Edge, Topology},
+    Direction,
+};
+use Direction::*;
+
+const DIRECTIONS: [Direction; 4] = [R, D, L, U];
+
+/// The layout of the six faces in the map, as the (row, column) of each
+/// face counted in whole faces, in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Net {
+    pub edge_len: usize,
+    pub faces: Vec<(usize, usize)>,
+}
+impl Net {
+    /// Find the faces in the lines of the map. The face size follows from
+    /// the number of tiles, since there are six faces.
+    pub fn from_map(lines: &[&str]) -> AnyResult<Self> {
+        let tiles = lines
+            .iter()
+            .flat_map(|l| l.chars())
+            .filter(|&ch| ch != ' ')
+            .count();
+        let edge_len = ((tiles / 6) as f64).sqrt() as usize;
+        if edge_len == 0 || 6 * edge_len * edge_len != tiles {
+            bail!("{tiles} tiles can't be split into six square faces");
+        }
+
+        let is_tile = |row: usize, col: usize| {
+            lines
+                .get(row)
+                .and_then(|l| l.chars().nth(col))
+                .is_some_and(|ch| ch != ' ')
+        };
+
+        let mut faces = vec![];
+        let block_rows = lines.len() / edge_len;
+        let block_cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) / edge_len;
+        for br in 0..block_rows {
+            for bc in 0..block_cols {
+                if is_tile(br * edge_len, bc * edge_len) {
+                    faces.push((br, bc));
+                }
+            }
+        }
+
+        if faces.len() != 6 {
+            bail!("expected 6 faces, and have {}", faces.len());
+        }
+        Ok(Net { edge_len, faces })
+    }
+
+    fn neighbour(&self, face: usize, dir: Direction) -> Option<usize> {
+        let (br, bc) = self.faces[face];
+        let (dr, dc) = dir.delta();
+        let next = (
+            br.checked_add_signed(dr as isize)?,
+            bc.checked_add_signed(dc as isize)?,
+        );
+        self.faces.iter().position(|&f| f == next)
+    }
+
+    /// Fold the net into a cube, giving the connections between every
+    /// pair of edges. Faces are numbered from 1 in reading order.
+    pub fn fold(&self) -> AnyResult<Topology> {
+        let mut orientations = vec![None; self.faces.len()];
+        orientations[0] = Some(Orientation::FIRST);
+        let mut stack = vec![0];
+        while let Some(face) = stack.pop() {
+            let orientation = orientations[face].expect("visited");
+            for dir in DIRECTIONS {
+                if let Some(next) = self.neighbour(face, dir) {
+                    if orientations[next].is_none() {
+                        orientations[next] = Some(orientation.roll(dir));
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        let Some(orientations) = orientations.into_iter().collect::<Option<Vec<_>>>() else {
+            bail!("the faces of the net are not all joined");
+        };
+
+        let by_normal: HashMap<Vec3, usize> = orientations
+            .iter()
+            .enumerate()
+            .map(|(face, o)| (o.normal, face))
+            .collect();
+        if by_normal.len() != 6 {
+            bail!("the faces overlap when folded, so this is not a cube net");
+        }
+
+        let mut connections = vec![];
+        for (face, o) in orientations.iter().enumerate() {
+            for dir in DIRECTIONS {
+                // the face over this edge lies in the direction it points,
+                // and its own edge there points back towards this face
+                let other = by_normal[&o.towards(dir)];
+                let other_o = orientations[other];
+                let other_dir = DIRECTIONS
+                    .into_iter()
+                    .find(|&d| other_o.towards(d) == o.normal)
+                    .expect("adjacent faces share an edge");
+
+                if (face, dir) < (other, other_dir) {
+                    let invert = o.along(dir) != other_o.along(other_dir);
+                    connections.push(Connection::new(
+                        Edge::new(face + 1, dir),
+                        Edge::new(other + 1, other_dir),
+                        invert,
+                    ));
+                }
+            }
+        }
+        Topology::new(&connections)
+    }
+}
+
+type Vec3 = [i32; 3];
+
+fn neg(v: Vec3) -> Vec3 {
+    v.map(|x| -x)
+}
+
+/// Where a face ends up on the cube: the 3D directions of its rows and
+/// columns, and the way it faces.
+#[derive(Debug, Clone, Copy)]
+struct Orientation {
+    right: Vec3,
+    down: Vec3,
+    normal: Vec3,
+}
+impl Orientation {
+    const FIRST: Self = Orientation {
+        right: [1, 0, 0],
+        down: [0, 1, 0],
+        normal: [0, 0, 1],
+    };
+
+    /// The 3D direction of heading across the face in `dir`.
+    fn towards(&self, dir: Direction) -> Vec3 {
+        match dir {
+            R => self.right,
+            L => neg(self.right),
+            D => self.down,
+            U => neg(self.down),
+        }
+    }
+
+    /// The direction positions are counted in along the edge in `dir`:
+    /// rows for the left and right edges, columns for the top and bottom.
+    fn along(&self, dir: Direction) -> Vec3 {
+        match dir {
+            L | R => self.down,
+            U | D => self.right,
+        }
+    }
+
+    /// The orientation of the neighbouring face in `dir` in the net, once
+    /// the cube is folded along the edge between them.
+    fn roll(&self, dir: Direction) -> Self {
+        let Orientation {
+            right,
+            down,
+            normal,
+        } = *self;
+        match dir {
+            R => Orientation {
+                right: neg(normal),
+                down,
+                normal: right,
+            },
+            L => Orientation {
+                right: normal,
+                down,
+                normal: neg(right),
+            },
+            D => Orientation {
+                right,
+                down: neg(normal),
+                normal: down,
+            },
+            U => Orientation {
+                right,
+                down: normal,
+                normal: neg(down),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part2::Position, tests::TEST_INPUT};
+    use indoc::indoc;
+
+    fn net(layout: &str) -> Net {
+        let lines: Vec<_> = layout.lines().collect();
+        Net::from_map(&lines).unwrap()
+    }
+
+    #[test]
+    fn finds_test_faces() {
+        let lines: Vec<_> = TEST_INPUT.lines().take_while(|l| !l.is_empty()).collect();
+        let net = Net::from_map(&lines).unwrap();
+        assert_eq!(net.edge_len, 4);
+        assert_eq!(net.faces, [(0, 2), (1, 0), (1, 1), (1, 2), (2, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn folds_real_layout() {
+        // the shape of the puzzle input, as originally worked out on paper
+        let connections = [
+            Connection::new(Edge::new(1, U), Edge::new(6, L), false),
+            Connection::new(Edge::new(1, D), Edge::new(3, U), false),
+            Connection::new(Edge::new(1, L), Edge::new(4, L), true),
+            Connection::new(Edge::new(1, R), Edge::new(2, L), false),
+            Connection::new(Edge::new(2, U), Edge::new(6, D), false),
+            Connection::new(Edge::new(2, D), Edge::new(3, R), false),
+            Connection::new(Edge::new(2, R), Edge::new(5, R), true),
+            Connection::new(Edge::new(3, D), Edge::new(5, U), false),
+            Connection::new(Edge::new(3, L), Edge::new(4, U), false),
+            Connection::new(Edge::new(4, D), Edge::new(6, U), false),
+            Connection::new(Edge::new(4, R), Edge::new(5, L), false),
+            Connection::new(Edge::new(5, D), Edge::new(6, R), false),
+        ];
+        let layout = indoc! {"
+             ..
+             .
+            ..
+            .
+        "};
+        let topology = net(layout).fold().unwrap();
+        assert_eq!(topology, Topology::new(&connections).unwrap());
+    }
+
+    #[test]
+    fn folds_all_nets() {
+        let nets = [
+            // 1-4-1
+            ".\n....\n.",
+            ".\n....\n .",
+            ".\n....\n  .",
+            ".\n....\n   .",
+            " .\n....\n .",
+            " .\n....\n  .",
+            // 2-3-1
+            "..\n ...\n .",
+            "..\n ...\n  .",
+            "..\n ...\n   .",
+            // 2-2-2
+            "..\n ..\n  ..",
+            // 3-3
+            "...\n  ...",
+        ];
+        for layout in nets {
+            assert!(net(layout).fold().is_ok(), "failed to fold\n{layout}");
+
+            // with 2x2 faces, walking four edge lengths in a straight line
+            // goes once round the cube and back to the start
+            let mut notes = String::new();
+            for line in layout.lines() {
+                let doubled: String = line.chars().flat_map(|ch| [ch, ch]).collect();
+                notes += &format!("{doubled}\n{doubled}\n");
+            }
+            let problem = crate::part2::parse_input(&(notes + "\n1\n")).unwrap();
+            for dir in DIRECTIONS {
+                let start = Position {
+                    face: 0,
+                    r: 1,
+                    c: 0,
+                    dir,
+                };
+                let mut pos = start;
+                for _ in 0..8 {
+                    pos = problem.next_position(pos);
+                }
+                assert_eq!(pos, start, "walking {dir} on\n{layout}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_non_cube_nets() {
+        assert!(net("......").fold().is_err());
+        assert!(net("....\n..").fold().is_err());
+    }
+}
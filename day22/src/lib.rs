@@ -1,8 +1,13 @@
+pub mod fold;
 pub mod part1;
 pub mod part2;
 
 use std::fmt::{Display, Write};
 
+use common::{
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
 use nalgebra::DMatrix;
 use BlockType::*;
 use Direction::*;
@@ -90,27 +95,119 @@ impl Display for BlockType {
         f.write_char(ch)
     }
 }
+impl BlockType {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            ' ' => Some(Empty),
+            '.' => Some(Open),
+            '#' => Some(Wall),
+            _ => None,
+        }
+    }
+}
+
+/// Split the notes into the lines of the map and the path to follow,
+/// checking the map only uses known tiles.
+pub fn split_notes(input: &str) -> Result<(Vec<&str>, Vec<Instruction>), ParseError> {
+    let src = Source::new(input);
+    let lines: Vec<_> = input.lines().collect();
+    let mut sections = lines.split(|l| l.is_empty());
+
+    let map_input = sections.next().filter(|m| !m.is_empty());
+    let map_input = src.require(map_input, input, "missing map")?;
+    for line in map_input {
+        if let Some((i, ch)) = line
+            .char_indices()
+            .find(|(_, ch)| BlockType::from_char(*ch).is_none())
+        {
+            return Err(src.error(&line[i..i + ch.len_utf8()], "unexpected map character"));
+        }
+    }
+
+    let inst_input = sections.next().and_then(|s| s.first());
+    let inst_input = inst_input.ok_or_else(|| src.end_error("missing path after the map"))?;
+    let instructions = parse_instructions(src, inst_input)?;
+
+    Ok((map_input.to_vec(), instructions))
+}
+
+// the path alternates numbers of tiles to move with L or R turns
+fn parse_instructions(src: Source, line: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions = vec![];
+    let mut rest = line;
+    while let Some(ch) = rest.chars().next() {
+        let len = match ch {
+            'L' | 'R' => {
+                instructions.push(match ch {
+                    'L' => Instruction::TurnLeft,
+                    _ => Instruction::TurnRight,
+                });
+                1
+            }
+            '0'..='9' => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                instructions.push(Instruction::Move(src.parse(&rest[..len])?));
+                len
+            }
+            _ => return Err(src.error(&rest[..ch.len_utf8()], "expected a number, L or R")),
+        };
+        rest = &rest[len..];
+    }
+    Ok(instructions)
+}
 
 pub type Map = DMatrix<BlockType>;
 
+pub struct Day22;
+impl Solution for Day22 {
+    type Input = (part1::Problem, part2::Problem);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        let flat = part1::parse_input(input)?;
+        let cube = part2::parse_input(input)?;
+        Ok((flat, cube))
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1::run(&input.0))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(input.1.run())
+    }
+}
+
+pub const DAY: Day = Day::new::<Day22>(22, env!("CARGO_MANIFEST_DIR"));
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use indoc::indoc;
 
     pub const TEST_INPUT: &str = indoc! {"
-            ...#
-            .#..
-            #...
-            ....
+                ...#
+                .#..
+                #...
+                ....
         ...#.......#
         ........#...
         ..#....#....
         ..........#.
-            ...#....
-            .....#..
-            .#......
-            ......#.
+                ...#....
+                .....#..
+                .#......
+                ......#.
 
         10R5L5R10L4R5L5
     "};
+
+    #[test]
+    fn bad_path_located() {
+        let err = split_notes(&TEST_INPUT.replace("5L5R", "5L5X")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (14, 7, "X"));
+    }
 }
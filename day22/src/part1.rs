@@ -1,8 +1,6 @@
-use crate::{BlockType, Direction, Instruction, Map};
-use anyhow::bail;
+use crate::{split_notes, BlockType, Direction, Instruction, Map};
 use common::*;
 use nalgebra::DMatrix;
-use regex::Regex;
 
 use BlockType::*;
 use Direction::*;
@@ -33,12 +31,7 @@ impl Problem {
 }
 
 pub fn parse_input(input: &str) -> AnyResult<Problem> {
-    let lines: Vec<_> = input.lines().collect();
-
-    let mut sections = lines.split(|l| l.is_empty());
-
-    let map_input = sections.next().ok_anyhow()?;
-    let inst_input = sections.next().ok_anyhow()?;
+    let (map_input, instructions) = split_notes(input)?;
 
     let rows = map_input.len();
     let cols = map_input
@@ -51,29 +44,7 @@ pub fn parse_input(input: &str) -> AnyResult<Problem> {
     for row in 0..rows {
         let line = map_input[row];
         for (col, ch) in line.chars().enumerate() {
-            let block_type = match ch {
-                ' ' => Empty,
-                '.' => Open,
-                '#' => Wall,
-                _ => bail!("unexpected map character: {}", ch),
-            };
-            map[(row, col)] = block_type;
-        }
-    }
-
-    let mut instructions = vec![];
-    let re = Regex::new(r#"(\d+)|(R|L)"#)?;
-    for c in re.captures_iter(inst_input.first().ok_anyhow()?) {
-        if let Some(count) = c.get(1) {
-            instructions.push(Instruction::Move(count.as_str().parse()?))
-        }
-        if let Some(turn) = c.get(2) {
-            let dir = match turn.as_str() {
-                "L" => Instruction::TurnLeft,
-                "R" => Instruction::TurnRight,
-                x => bail!("invalid instruction: {}", x),
-            };
-            instructions.push(dir);
+            map[(row, col)] = BlockType::from_char(ch).expect("checked by split_notes");
         }
     }
 
@@ -102,7 +73,7 @@ pub fn run(problem: &Problem) -> usize {
         }
     }
 
-    let score = 1000 * (pos.0 + 1)
+    1000 * (pos.0 + 1)
         + 4 * (pos.1 + 1)
         + match dir {
             // Facing is 0 for right (>), 1 for down (v), 2 for left (<), and 3 for up (^)
@@ -110,9 +81,7 @@ pub fn run(problem: &Problem) -> usize {
             D => 1,
             L => 2,
             U => 3,
-        };
-    println!("final position {pos:?} and direction {dir:?}; score: {score}");
-    score
+        }
 }
 
 #[cfg(test)]
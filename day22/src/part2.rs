@@ -1,12 +1,10 @@
 use std::{collections::HashMap, fmt::Display};
 
-use crate::{BlockType, Direction, Instruction, Map};
-use anyhow::bail;
+use crate::{fold::Net, split_notes, BlockType, Direction, Instruction, Map};
+use anyhow::{anyhow, bail};
 use common::*;
 use nalgebra::DMatrix;
-use regex::Regex;
 
-use BlockType::*;
 use Direction::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -41,7 +39,7 @@ impl Connection {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Topology(HashMap<Edge, (Edge, bool)>);
 impl Topology {
     pub fn new(connections: &[Connection]) -> AnyResult<Self> {
@@ -185,9 +183,8 @@ impl Problem {
         // get the origin row and column for this face
         let face_num = pos.face;
         let (face_origin_r, face_origin_c) = self.faces_top_left[face_num];
-        println!("face origin: {face_origin_r}, {face_origin_c}");
 
-        let score = 1000 * (pos.r + face_origin_r + 1)
+        1000 * (pos.r + face_origin_r + 1)
             + 4 * (pos.c + face_origin_c + 1)
             + match pos.dir {
                 // Facing is 0 for right (>), 1 for down (v), 2 for left (<), and 3 for up (^)
@@ -195,9 +192,7 @@ impl Problem {
                 D => 1,
                 L => 2,
                 U => 3,
-            };
-        println!("score: {score}");
-        score
+            }
     }
 }
 
@@ -210,13 +205,7 @@ pub fn parse_block(
     for row in 0..edge_len {
         let line = lines[row];
         for (col, ch) in line.chars().skip(start_idx).take(edge_len).enumerate() {
-            let block_type = match ch {
-                ' ' => Empty,
-                '.' => Open,
-                '#' => Wall,
-                _ => panic!("invalid map character: {}", ch),
-            };
-            map[(row, col)] = block_type;
+            map[(row, col)] = BlockType::from_char(ch).expect("checked by split_notes");
         }
     }
 
@@ -227,61 +216,31 @@ pub fn parse_block(
     Some(map)
 }
 
-pub fn parse_input(input: &str, edge_len: usize, topology: Topology) -> AnyResult<Problem> {
-    let lines: Vec<_> = input.lines().collect();
-
-    let mut sections = lines.split(|l| l.is_empty());
-
-    let map_input = sections.next().ok_anyhow()?;
-    let inst_input = sections.next().ok_anyhow()?;
-
-    let total_rows = map_input.len();
-    let total_cols = map_input
-        .iter()
-        .map(|l| l.chars().count())
-        .max()
-        .ok_anyhow()?;
-
-    let block_rows = total_rows / edge_len;
-    let block_cols = total_cols / edge_len;
+pub fn parse_input(input: &str) -> AnyResult<Problem> {
+    let (map_input, instructions) = split_notes(input)?;
+    let net = Net::from_map(&map_input)?;
+    let edge_len = net.edge_len;
 
     let mut faces: Vec<DMatrix<BlockType>> = vec![];
     let mut faces_top_left = vec![];
-    for br in 0..block_rows {
+    for &(br, bc) in &net.faces {
         let lines = &map_input[edge_len * br..edge_len * (br + 1)];
-        for bc in 0..block_cols {
-            if let Some(face) = parse_block(lines, edge_len * bc, edge_len) {
-                faces.push(face);
-                faces_top_left.push((edge_len * br, edge_len * bc));
-            }
-        }
-    }
-
-    if faces.len() != 6 {
-        bail!("expected 6 faces, and have {}", faces.len());
-    }
-
-    let mut instructions = vec![];
-    let re = Regex::new(r#"(\d+)|(R|L)"#)?;
-    for c in re.captures_iter(inst_input.first().ok_anyhow()?) {
-        if let Some(count) = c.get(1) {
-            instructions.push(Instruction::Move(count.as_str().parse()?))
-        }
-        if let Some(turn) = c.get(2) {
-            let dir = match turn.as_str() {
-                "L" => Instruction::TurnLeft,
-                "R" => Instruction::TurnRight,
-                x => bail!("invalid instruction: {}", x),
-            };
-            instructions.push(dir);
-        }
+        let face = parse_block(lines, edge_len * bc, edge_len).ok_or_else(|| {
+            anyhow!(
+                "face at row {} column {} has gaps",
+                edge_len * br,
+                edge_len * bc
+            )
+        })?;
+        faces.push(face);
+        faces_top_left.push((edge_len * br, edge_len * bc));
     }
 
     Ok(Problem {
         edge_len,
         faces,
         faces_top_left,
-        topology,
+        topology: net.fold()?,
         instructions,
     })
 }
@@ -296,14 +255,14 @@ mod tests {
             Connection::new(Edge::new(4, U), Edge::new(1, D), false),
             Connection::new(Edge::new(4, D), Edge::new(5, U), false),
             Connection::new(Edge::new(4, L), Edge::new(3, R), false),
-            Connection::new(Edge::new(4, R), Edge::new(6, U), false),
+            Connection::new(Edge::new(4, R), Edge::new(6, U), true),
             Connection::new(Edge::new(3, U), Edge::new(1, L), false),
-            Connection::new(Edge::new(3, D), Edge::new(5, L), false),
+            Connection::new(Edge::new(3, D), Edge::new(5, L), true),
             Connection::new(Edge::new(3, L), Edge::new(2, R), false),
-            Connection::new(Edge::new(2, U), Edge::new(1, U), false),
-            Connection::new(Edge::new(2, D), Edge::new(5, D), false),
-            Connection::new(Edge::new(2, L), Edge::new(6, D), false),
-            Connection::new(Edge::new(1, R), Edge::new(6, R), false),
+            Connection::new(Edge::new(2, U), Edge::new(1, U), true),
+            Connection::new(Edge::new(2, D), Edge::new(5, D), true),
+            Connection::new(Edge::new(2, L), Edge::new(6, D), true),
+            Connection::new(Edge::new(1, R), Edge::new(6, R), true),
             Connection::new(Edge::new(5, R), Edge::new(6, L), false),
         ];
         Topology::new(&connections).unwrap()
@@ -314,9 +273,15 @@ mod tests {
         create_test_topology();
     }
 
+    #[test]
+    fn folded_topology_matches_sketch() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(problem.topology, create_test_topology());
+    }
+
     #[test]
     fn parse_input_correct() {
-        let problem = parse_input(TEST_INPUT, 4, create_test_topology()).unwrap();
+        let problem = parse_input(TEST_INPUT).unwrap();
         problem.faces.iter().enumerate().for_each(|(i, f)| {
             println!("{i}");
             println!("{f}");
@@ -325,7 +290,7 @@ mod tests {
 
     #[test]
     fn part2_correct() {
-        let problem = parse_input(TEST_INPUT, 4, create_test_topology()).unwrap();
+        let problem = parse_input(TEST_INPUT).unwrap();
         let res = problem.run();
         assert_eq!(res, 5031);
     }
@@ -0,0 +1,321 @@
+use anyhow::bail;
+use common::{
+    geometry::{self, Dir4},
+    parse::Source,
+    AnyResult, Day, Solution,
+};
+use rustc_hash::FxHashMap;
+use rustc_hash::FxHashSet;
+use std::fmt::{Display, Write};
+
+pub type Point = geometry::Point<i64>;
+
+const NUM_DIRECTIONS: usize = 4;
+const DIRECTIONS: [Dir4; NUM_DIRECTIONS] = [Dir4::N, Dir4::S, Dir4::W, Dir4::E];
+
+// the point moved to, plus the two either side of it
+fn adjacent_points_move(current: Point, dir: Dir4) -> [Point; 3] {
+    let moved = current + dir.into();
+    [
+        moved,
+        moved + dir.clockwise().into(),
+        moved + dir.counter_clockwise().into(),
+    ]
+}
+
+#[derive(Debug, Clone)]
+struct Elf {
+    current_location: Point,
+    proposed_location: Option<Point>,
+}
+impl Elf {
+    fn new(loc: Point) -> Elf {
+        Elf {
+            current_location: loc,
+            proposed_location: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    elves: Vec<Elf>,
+    current_locations: FxHashSet<Point>,
+    proposed_location_counts: FxHashMap<Point, usize>,
+    next_move_cycle: usize,
+}
+impl Problem {
+    fn new_with_elves(elves: Vec<Elf>) -> Problem {
+        let current_locations = elves.iter().map(|e| e.current_location).collect();
+        Problem {
+            elves,
+            current_locations,
+            proposed_location_counts: FxHashMap::default(),
+            next_move_cycle: 0,
+        }
+    }
+
+    fn bounding_box(&self) -> (Point, Point) {
+        let first = self.elves.first().unwrap();
+
+        let mut min_x = first.current_location.x;
+        let mut max_x = min_x;
+
+        let mut min_y = first.current_location.y;
+        let mut max_y = min_y;
+
+        for elf in &self.elves {
+            min_x = min_x.min(elf.current_location.x);
+            max_x = max_x.max(elf.current_location.x);
+
+            min_y = min_y.min(elf.current_location.y);
+            max_y = max_y.max(elf.current_location.y);
+        }
+        (Point::new(min_x, min_y), Point::new(max_x, max_y))
+    }
+
+    fn count_empty_blocks(&self) -> usize {
+        let bb = self.bounding_box();
+        let mut empty = 0;
+        for x in bb.0.x..=bb.1.x {
+            for y in bb.0.y..=bb.1.y {
+                if !self.current_locations.contains(&Point::new(x, y)) {
+                    empty += 1;
+                }
+            }
+        }
+        empty
+    }
+
+    fn step_once(&mut self) -> usize {
+        // phase 1 -- proposed moves
+        self.proposed_location_counts.clear();
+
+        for i in 0..self.elves.len() {
+            let elf = self.elves.get_mut(i).unwrap();
+
+            let mut updated_position = None;
+
+            // elf wants to move if there are any elves adjacent to it
+            let elf_wants_to_move = elf
+                .current_location
+                .neighbours8()
+                .iter()
+                .any(|p| self.current_locations.contains(p));
+
+            // determine the proposed move
+            if elf_wants_to_move {
+                for dir in DIRECTIONS
+                    .iter()
+                    .cycle()
+                    .skip(self.next_move_cycle)
+                    .take(NUM_DIRECTIONS)
+                {
+                    let adjacent = adjacent_points_move(elf.current_location, *dir);
+                    if !adjacent.iter().any(|p| self.current_locations.contains(p)) {
+                        // available: take this as the elf's proposed move
+                        updated_position = Some(elf.current_location + Point::from(*dir));
+                        break;
+                    }
+                }
+            }
+
+            if let Some(pos) = updated_position {
+                elf.proposed_location = Some(pos);
+                *self.proposed_location_counts.entry(pos).or_default() += 1;
+            } else {
+                elf.proposed_location = None;
+            }
+        }
+
+        // phase 2 -- move to proposed locations if possible
+        let mut count_moved = 0;
+        for i in 0..self.elves.len() {
+            let elf = self.elves.get_mut(i).unwrap();
+
+            if let Some(pos) = elf.proposed_location {
+                // move if the elf is the only one proposing the new location
+                let dest_count = self.proposed_location_counts.get(&pos).unwrap();
+                if *dest_count == 1 {
+                    elf.current_location = pos;
+                    count_moved += 1;
+                }
+            }
+        }
+
+        // and finally update the map
+        self.current_locations.clear();
+        for elf in &self.elves {
+            self.current_locations.insert(elf.current_location);
+        }
+
+        // next move cycle
+        self.next_move_cycle = (self.next_move_cycle + 1) % NUM_DIRECTIONS;
+
+        count_moved
+    }
+}
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bb = self.bounding_box();
+        for y in bb.0.y..=bb.1.y {
+            for x in bb.0.x..=bb.1.x {
+                if self.current_locations.contains(&Point::new(x, y)) {
+                    f.write_char('#')?;
+                } else {
+                    f.write_char('.')?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_input(input: &str) -> AnyResult<Problem> {
+    let src = Source::new(input);
+    let mut elves = vec![];
+    for (y, line) in input.lines().enumerate() {
+        for (x, (i, ch)) in line.char_indices().enumerate() {
+            match ch {
+                '#' => elves.push(Elf::new(Point::new(x as i64, y as i64))),
+                '.' => {}
+                _ => {
+                    let span = &line[i..i + ch.len_utf8()];
+                    return Err(src.error(span, "expected '#' or '.'").into());
+                }
+            }
+        }
+    }
+    Ok(Problem::new_with_elves(elves))
+}
+
+fn part1(problem: &Problem) -> usize {
+    let mut problem = problem.clone();
+    for _ in 1..=10 {
+        problem.step_once();
+    }
+    problem.count_empty_blocks()
+}
+
+fn part2(problem: &Problem) -> AnyResult<usize> {
+    let mut problem = problem.clone();
+    for round in 1.. {
+        if problem.step_once() == 0 {
+            return Ok(round);
+        }
+    }
+    bail!("not found");
+}
+
+pub struct Day23;
+impl Solution for Day23 {
+    type Input = Problem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input)
+    }
+}
+
+pub const DAY: Day = Day::new::<Day23>(23, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        ..............
+        ..............
+        .......#......
+        .....###.#....
+        ...#...#.#....
+        ....#...##....
+        ...#.###......
+        ...##.#.##....
+        ....#..#......
+        ..............
+        ..............
+        ..............
+    "};
+
+    const TEST_INPUT_SMALL: &str = indoc! {"
+        .....
+        ..##.
+        ..#..
+        .....
+        ..##.
+        .....
+    "};
+
+    #[test]
+    fn parse_input_correct() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        dbg!(&problem);
+        println!("{}", problem);
+    }
+
+    #[test]
+    fn step_once_check_small() {
+        let mut problem = parse_input(TEST_INPUT_SMALL).unwrap();
+        println!("{problem}");
+        for i in 1..=3 {
+            let count = problem.step_once();
+            println!("i: {i}, moved: {count}");
+            println!("{problem}");
+        }
+        let expected = indoc! {"
+            ..#..
+            ....#
+            #....
+            ....#
+            .....
+            ..#..
+        "};
+        assert_eq!(problem.to_string(), expected);
+    }
+
+    #[test]
+    fn step_once_check_larger() {
+        let mut problem = parse_input(TEST_INPUT).unwrap();
+        println!("{problem}");
+        for i in 1..=10 {
+            let count = problem.step_once();
+            println!("i: {i}, moved: {count}");
+            println!("{problem}");
+        }
+
+        let expected = indoc! {"
+            ......#.....
+            ..........#.
+            .#.#..#.....
+            .....#......
+            ..#.....#..#
+            #......##...
+            ....##......
+            .#........#.
+            ...#.#..#...
+            ............
+            ...#..#..#..
+        "};
+        assert_eq!(problem.to_string(), expected);
+        assert_eq!(problem.count_empty_blocks(), 110);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let problem = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&problem).unwrap();
+        assert_eq!(res, 20);
+    }
+}
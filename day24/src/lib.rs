@@ -1,89 +1,26 @@
 pub mod part1;
 pub mod part2;
 
-use std::{
-    fmt::{Debug, Display, Write},
-    ops::{Add, Mul, Sub},
-};
+use std::fmt::{Debug, Display, Write};
 
 use arrayvec::ArrayVec;
-use common::*;
+use common::{
+    geometry::{self, Dir4, Grid},
+    *,
+};
 use gcd::Gcd;
 use itertools::Itertools;
-use nalgebra::DMatrix;
-
-#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
-pub struct Point {
-    pub x: i64,
-    pub y: i64,
-}
-impl Point {
-    pub const fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    pub fn to_coord(self) -> (usize, usize) {
-        self.into()
-    }
-}
-impl Add for Point {
-    type Output = Point;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Point::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-impl Sub for Point {
-    type Output = Point;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Point::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
-impl Mul for Point {
-    type Output = Point;
-
-    fn mul(self, rhs: Self) -> Self::Output {
-        Point::new(self.x * rhs.x, self.y * rhs.y)
-    }
-}
-
-impl From<Dir> for Point {
-    fn from(value: Dir) -> Self {
-        match value {
-            Dir::N => Point::new(0, -1),
-            Dir::S => Point::new(0, 1),
-            Dir::W => Point::new(-1, 0),
-            Dir::E => Point::new(1, 0),
-        }
-    }
-}
-
-impl From<Point> for (usize, usize) {
-    fn from(value: Point) -> (usize, usize) {
-        let x = value.x.try_into().expect("invalid x coordinate");
-        let y = value.y.try_into().expect("invalid y coordinate");
-        // note matrix coordinates are (row,col)
-        (y, x)
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
-pub enum Dir {
-    N,
-    S,
-    W,
-    E,
-}
+pub type Point = geometry::Point<i64>;
 
 #[derive(Debug, Clone)]
 pub struct Blizzard {
     origin: Point,
-    dir: Dir,
+    dir: Dir4,
 }
 impl Blizzard {
     pub fn location_at_time(&self, time: usize, rows: usize, cols: usize) -> Point {
-        let delta = Point::from(self.dir) * Point::new(time as i64, time as i64);
+        let delta = Point::from(self.dir) * time as i64;
         let current = self.origin + delta;
         Point::new(
             current.x.rem_euclid(cols as i64),
@@ -118,7 +55,7 @@ impl Problem {
 #[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
 pub enum GridState {
     Blank,
-    One(Dir),
+    One(Dir4),
     Multiple(usize),
 }
 impl Display for GridState {
@@ -126,10 +63,10 @@ impl Display for GridState {
         match self {
             GridState::Blank => f.write_char('.'),
             GridState::One(d) => match d {
-                Dir::N => f.write_char('^'),
-                Dir::S => f.write_char('v'),
-                Dir::W => f.write_char('<'),
-                Dir::E => f.write_char('>'),
+                Dir4::N => f.write_char('^'),
+                Dir4::S => f.write_char('v'),
+                Dir4::W => f.write_char('<'),
+                Dir4::E => f.write_char('>'),
             },
             GridState::Multiple(m) => match m {
                 0..=9 => write!(f, "{m}"),
@@ -142,15 +79,14 @@ impl Display for GridState {
 #[derive(Debug, Clone)]
 pub struct ProblemState<'a> {
     problem: &'a Problem,
-    grid: DMatrix<GridState>,
+    grid: Grid<GridState>,
 }
 impl<'a> ProblemState<'a> {
-    pub fn with_time(problem: &Problem, time: usize) -> ProblemState {
-        let mut grid = DMatrix::from_element(problem.rows, problem.cols, GridState::Blank);
+    pub fn with_time(problem: &Problem, time: usize) -> ProblemState<'_> {
+        let mut grid = Grid::new(problem.cols, problem.rows, GridState::Blank);
 
         for bliz in &problem.blizzards {
             let loc = bliz.location_at_time(time, problem.rows, problem.cols);
-            let loc: (usize, usize) = loc.try_into().unwrap();
             grid[loc] = match grid[loc] {
                 GridState::Blank => GridState::One(bliz.dir),
                 GridState::One(_) => GridState::Multiple(2),
@@ -165,11 +101,11 @@ impl<'a> ProblemState<'a> {
         let mut avail = ArrayVec::new();
 
         let deltas = [
-            Point::new(0, 0),
-            Dir::N.into(),
-            Dir::E.into(),
-            Dir::S.into(),
-            Dir::W.into(),
+            Point::zero(),
+            Dir4::N.into(),
+            Dir4::E.into(),
+            Dir4::S.into(),
+            Dir4::W.into(),
         ];
 
         // check directions - can move into a blank space, or to start or end
@@ -178,7 +114,7 @@ impl<'a> ProblemState<'a> {
             let valid = match new_loc {
                 p if p == self.problem.start => true,
                 p if p == self.problem.end => true,
-                p => self.problem.contains(p) && self.grid[p.to_coord()] == GridState::Blank,
+                p => self.grid.get(p) == Some(&GridState::Blank),
             };
             if valid {
                 avail.push(new_loc);
@@ -190,62 +126,66 @@ impl<'a> ProblemState<'a> {
 
 impl<'a> Display for ProblemState<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.problem.rows {
-            for x in 0..self.problem.cols {
-                let g = self.grid[(y, x)];
-                Display::fmt(&g, f)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        Display::fmt(&self.grid, f)
     }
 }
 
 pub fn parse_input(input: &str) -> AnyResult<Problem> {
+    let src = parse::Source::new(input);
     let lines = input.lines().collect_vec();
+    let width = lines.first().map_or(0, |l| l.chars().count());
+    if lines.len() < 3 || width < 3 {
+        return Err(src
+            .end_error("expected a valley surrounded by walls")
+            .into());
+    }
     let rows = lines.len() - 2;
-    let cols = lines.first().ok_anyhow()?.len() - 2;
+    let cols = width - 2;
 
     let gcd = rows.gcd(cols);
     let cycle_length = rows * cols / gcd;
-    println!("rows {rows} cols {cols} gcd {gcd} cycle {cycle_length}");
 
     let mut blizzards = vec![];
     for y in 0..rows {
         let line = lines[y + 1];
-        for (x, ch) in line.chars().skip(1).take(cols).enumerate() {
+        let line_width = line.chars().count();
+        if line_width != width {
+            let message = format!("row has width {line_width}, expected {width}");
+            return Err(src.error(line, message).into());
+        }
+
+        for (x, (i, ch)) in line.char_indices().enumerate() {
+            let wall = x == 0 || x == cols + 1;
             let dir = match ch {
-                '>' => Some(Dir::E),
-                '<' => Some(Dir::W),
-                '^' => Some(Dir::N),
-                'v' => Some(Dir::S),
-                _ => None,
+                '#' if wall => continue,
+                '.' if !wall => continue,
+                '>' if !wall => Dir4::E,
+                '<' if !wall => Dir4::W,
+                '^' if !wall => Dir4::N,
+                'v' if !wall => Dir4::S,
+                _ => {
+                    let span = &line[i..i + ch.len_utf8()];
+                    return Err(src.error(span, "unexpected character").into());
+                }
             };
-
-            if let Some(d) = dir {
-                blizzards.push(Blizzard {
-                    origin: Point::new(x as i64, y as i64),
-                    dir: d,
-                })
-            }
+            blizzards.push(Blizzard {
+                origin: Point::new(x as i64 - 1, y as i64),
+                dir,
+            })
         }
     }
 
-    let start_x = lines
-        .first()
-        .ok_anyhow()?
-        .chars()
-        .position(|c| c == '.')
-        .ok_anyhow()?
-        - 1;
-
-    let end_x = lines
-        .last()
-        .ok_anyhow()?
-        .chars()
-        .position(|c| c == '.')
-        .ok_anyhow()?
-        - 1;
+    // the start and end are the gaps in the top and bottom walls
+    let gap = |line: &str| {
+        let x = line.chars().position(|c| c == '.');
+        src.require(
+            x.filter(|x| (1..=cols).contains(x)),
+            line,
+            "missing gap in the wall",
+        )
+    };
+    let start_x = gap(lines[0])? - 1;
+    let end_x = gap(lines[rows + 1])? - 1;
 
     Ok(Problem {
         rows,
@@ -256,3 +196,24 @@ pub fn parse_input(input: &str) -> AnyResult<Problem> {
         end: Point::new(end_x as i64, rows as i64),
     })
 }
+
+pub struct Day24;
+impl Solution for Day24 {
+    type Input = Problem;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1::find_shortest_path(input).ok_anyhow()
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2::find_shortest_path(input).ok_anyhow()
+    }
+}
+
+pub const DAY: Day = Day::new::<Day24>(24, env!("CARGO_MANIFEST_DIR"));
@@ -1,39 +1,11 @@
-use common::*;
-use day24::{parse_input, part1, part2, ProblemState};
-
-fn main() -> AnyResult<()> {
-    let input = read_file("day24/input.txt")?;
-
-    let problem = parse_input(&input)?;
-
-    for t in 0..3 {
-        let state = ProblemState::with_time(&problem, t);
-        println!("time = {t}");
-        println!("{state}");
-    }
-
-    let init = ProblemState::with_time(&problem, 0);
-    for cycle in 0..3 {
-        let t = cycle * problem.cycle_length();
-        let state = ProblemState::with_time(&problem, t);
-        println!("cycle {cycle} time = {t}");
-        println!("{state}");
-
-        assert_eq!(init.to_string(), state.to_string());
-    }
-
-    let part1_result = part1::find_shortest_path(&problem);
-    println!("part1 result: {part1_result:?}");
-
-    let part2_result = part2::find_shortest_path(&problem);
-    println!("part2 result: {part2_result:?}");
-
-    Ok(())
+fn main() -> anyhow::Result<()> {
+    common::run_day(&day24::DAY)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use common::cycle::History;
+    use day24::{parse_input, part1, part2, ProblemState};
     use indoc::indoc;
 
     const TEST_INPUT_BASIC: &str = indoc! {"
@@ -87,6 +59,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cycle_length_detected() {
+        let problem = parse_input(TEST_INPUT_COMPLEX).unwrap();
+        let mut history = History::new();
+        let cycle = (0..)
+            .find_map(|t| history.push(ProblemState::with_time(&problem, t).to_string(), t))
+            .unwrap();
+        assert_eq!(cycle.start, 0);
+        assert_eq!(cycle.length, problem.cycle_length());
+    }
+
     #[test]
     fn run_cycles_simple() {
         test_cycles(TEST_INPUT_COMPLEX);
@@ -1,8 +1,7 @@
 use std::fmt::Debug;
 
+use common::search::astar;
 use itertools::Itertools;
-use priority_queue::PriorityQueue;
-use rustc_hash::FxHashMap;
 
 use crate::*;
 
@@ -30,69 +29,45 @@ impl PosStateRegime {
 }
 
 pub fn find_shortest_path(problem: &Problem) -> Option<i32> {
-    const DIST_INIT: i32 = i32::MAX / 2;
-    const PRIO_INIT: i32 = i32::MIN;
-
     let states = (0..problem.cycle_length)
         .map(|t| ProblemState::with_time(problem, t))
         .collect_vec();
 
-    let mut dist: FxHashMap<PosStateRegime, i32> = FxHashMap::default();
-
-    // initialise
-    let start = PosStateRegime::new(Regime::Initial, 0, problem.start);
-    let mut queue = PriorityQueue::new();
-    queue.push(start, 0);
-    dist.insert(start, 0);
-
-    while let Some((u, _prio)) = queue.pop() {
-        let u_dist = dist.get(&u).copied().unwrap();
-
+    let successors = |u: &PosStateRegime| {
         let next_phase = problem.next_phase(u.phase);
         let next_state = &states[next_phase];
+        let regime = u.regime;
+        next_state
+            .available_moves(u.loc)
+            .into_iter()
+            .map(move |v_point| {
+                // transition points from one Regime to the next; this is only possible
+                // at the specific points we allow below, so it forces us to pass through
+                // these points
+                let next_regime = match (regime, v_point) {
+                    (Regime::Initial, p) if p == problem.end => Regime::ReturnToStart,
+                    (Regime::ReturnToStart, p) if p == problem.start => Regime::Final,
+                    (r, _) => r,
+                };
+                (PosStateRegime::new(next_regime, next_phase, v_point), 1)
+            })
+    };
 
-        let valid_moves = next_state.available_moves(u.loc);
-        for v_point in valid_moves {
-            // transition points from one Regime to the next; this is only possible
-            // at the specific points we allow below, so it forces us to pass through
-            // these points
-            let next_regime = match (u.regime, v_point) {
-                (Regime::Initial, p) if p == problem.end => Regime::ReturnToStart,
-                (Regime::ReturnToStart, p) if p == problem.start => Regime::Final,
-                (r, _) => r,
-            };
-            let v = PosStateRegime::new(next_regime, next_phase, v_point);
+    // straight-line distance for the rest of the current leg, plus any legs
+    // still to come
+    let leg = problem.start.manhattan_distance(problem.end) as i32;
+    let heuristic = |u: &PosStateRegime| {
+        let (target, legs_after) = match u.regime {
+            Regime::Initial => (problem.end, 2),
+            Regime::ReturnToStart => (problem.start, 1),
+            Regime::Final => (problem.end, 0),
+        };
+        u.loc.manhattan_distance(target) as i32 + legs_after * leg
+    };
 
-            // add point to dist map and queue if it's unseen
-            let v_dist = dist.entry(v).or_insert_with(|| {
-                queue.push(v, PRIO_INIT);
-                DIST_INIT
-            });
+    // we're done when we reach the `end` point in the `Final` regime, in any phase
+    let is_goal = |u: &PosStateRegime| u.regime == Regime::Final && u.loc == problem.end;
 
-            // update shortest path to `v`
-            if queue.get(&v).is_some() {
-                // distance is to current node (u) + 1
-                let alt = u_dist + 1;
-                if alt < *v_dist {
-                    // update distances to this node
-                    *v_dist = alt;
-                    queue.change_priority(&v, -alt);
-                }
-            }
-        }
-    }
-
-    // print out all states
-    // for dest_state in (0..problem.cycle_length)
-    //     .map(|phase| PosStateRegime::new(Regime::Final, phase, problem.end))
-    // {
-    //     println!("state {dest_state:?} => {:?}", dist.get(&dest_state));
-    // }
-
-    // return shortest - from the `Final` regime, and we're looking for the `end` point
-    // in any phase.
-    let minimum = (0..problem.cycle_length)
-        .flat_map(|phase| dist.get(&PosStateRegime::new(Regime::Final, phase, problem.end)))
-        .min();
-    minimum.copied()
+    let start = PosStateRegime::new(Regime::Initial, 0, problem.start);
+    astar([start], successors, heuristic, is_goal).goal_cost()
 }
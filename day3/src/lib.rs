@@ -0,0 +1,231 @@
+use anyhow::{anyhow, bail};
+use common::{
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Item(char);
+
+impl TryFrom<char> for Item {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> anyhow::Result<Item> {
+        match value {
+            'a'..='z' => Ok(Item(value)),
+            'A'..='Z' => Ok(Item(value)),
+            _ => bail!("invalid item type"),
+        }
+    }
+}
+
+impl Item {
+    pub fn inner(&self) -> char {
+        self.0
+    }
+
+    pub fn priority(&self) -> i32 {
+        match self.inner() {
+            'a'..='z' => 1 + (self.inner() as i32 - 'a' as i32),
+            'A'..='Z' => 27 + (self.inner() as i32 - 'A' as i32),
+            _ => {
+                panic!("invalid char - this invariant should have been ensured by the constructor")
+            }
+        }
+    }
+}
+
+fn split_compartments<T>(rucksack: &[T]) -> (&[T], &[T]) {
+    let len = rucksack.len();
+    let middle = len / 2;
+    rucksack.split_at(middle)
+}
+
+fn parse_items(src: Source, items_str: &str) -> Result<Vec<Item>, ParseError> {
+    items_str
+        .char_indices()
+        .map(|(i, ch)| {
+            Item::try_from(ch).map_err(|e| src.error(&items_str[i..i + ch.len_utf8()], e))
+        })
+        .collect()
+}
+
+fn first_common_item(comp1: &[Item], comp2: &[Item]) -> Option<Item> {
+    // just do the dumb linear search for N^2 complexity; these are pretty
+    // small sets.
+    for i1 in comp1.iter().copied() {
+        for i2 in comp2.iter().copied() {
+            if i1 == i2 {
+                return Some(i1);
+            }
+        }
+    }
+
+    // no common items
+    None
+}
+
+/// Find a single common item in the supplied groups.
+///
+/// For those following along
+/// - this method is generic over both the Iterator `I` and the type returned by the iterator `A`.
+/// - `A` in turn implements `AsRef<[Item]>`, which means that it can be converted into a slice
+///   `&[Item]`
+/// - this means that the caller does not need to convert something like a `Vec` to a slice before calling,
+///   since `Vec` implements `AsRef<[T]>`
+///
+/// Implementation details: we collect the items in each rucksack into a corresponding `HashSet`, and
+/// then reduce the sets by intersection. Finally, we check that we have a unique item and return this.
+fn single_common_item_general<'a, I, A>(groups: I) -> Option<Item>
+where
+    I: Iterator<Item = &'a A>,
+    A: AsRef<[Item]> + 'a + ?Sized,
+{
+    let common = groups
+        .map(|grp| grp.as_ref().iter().copied().collect::<HashSet<Item>>())
+        .reduce(|acc, item| acc.intersection(&item).copied().collect())?;
+
+    if common.len() == 1 {
+        common.into_iter().next()
+    } else {
+        None
+    }
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<Vec<Item>>> {
+    let src = Source::new(input);
+    let rucksacks = input
+        .lines()
+        .map(|line| parse_items(src, line))
+        .collect::<Result<_, _>>()?;
+    Ok(rucksacks)
+}
+
+fn part1(rucksacks: &[Vec<Item>]) -> anyhow::Result<i32> {
+    let mut common_items = Vec::new();
+    for r in rucksacks {
+        let (items1, items2) = split_compartments(r);
+        let found_common =
+            first_common_item(items1, items2).ok_or_else(|| anyhow!("common item not found"))?;
+
+        common_items.push(found_common);
+    }
+
+    let sum: i32 = common_items.iter().map(Item::priority).sum();
+    Ok(sum)
+}
+
+fn part2(rucksacks: &[Vec<Item>]) -> anyhow::Result<i32> {
+    let mut sum = 0;
+
+    for group in rucksacks.chunks_exact(3) {
+        let common = single_common_item_general(group.iter())
+            .ok_or_else(|| anyhow!("no common item found"))?;
+
+        sum += common.priority();
+    }
+
+    Ok(sum)
+}
+
+pub struct Day3;
+impl Solution for Day3 {
+    type Input = Vec<Vec<Item>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        part2(input)
+    }
+}
+
+pub const DAY: Day = Day::new::<Day3>(3, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw
+    "};
+
+    const RUCKSACK: &str = "vJrwpWtwJgWrhcsFMMfFFhFp";
+    const COMPARTMENT1: &str = "vJrwpWtwJgWr";
+    const COMPARTMENT2: &str = "hcsFMMfFFhFp";
+
+    fn items(s: &str) -> Vec<Item> {
+        parse_items(Source::new(s), s).unwrap()
+    }
+
+    #[test]
+    fn correct_priority() {
+        assert_eq!(1, Item::try_from('a').unwrap().priority());
+        assert_eq!(26, Item::try_from('z').unwrap().priority());
+        assert_eq!(27, Item::try_from('A').unwrap().priority());
+        assert_eq!(52, Item::try_from('Z').unwrap().priority());
+    }
+
+    #[test]
+    fn invalid_item_located() {
+        let err = parse_input("abc\nde1f\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "1"));
+    }
+
+    #[test]
+    fn split_correct() {
+        let rucksack = items(RUCKSACK);
+        let (comp1, comp2) = split_compartments(&rucksack);
+        assert_eq!(comp1, items(COMPARTMENT1));
+        assert_eq!(comp2, items(COMPARTMENT2))
+    }
+
+    #[test]
+    fn common_item_correct() {
+        let comp1 = items(COMPARTMENT1);
+        let comp2 = items(COMPARTMENT2);
+
+        let found_common = first_common_item(&comp1, &comp2);
+        let expected = Some(Item::try_from('p').unwrap());
+        assert_eq!(found_common, expected);
+    }
+
+    #[test]
+    fn group_item_correct() {
+        let first_group = TEST_INPUT.lines().take(3);
+
+        let rucksacks: Vec<_> = first_group.map(items).collect();
+        let common_item = single_common_item_general(rucksacks.iter());
+
+        assert_eq!(common_item, Some(Item::try_from('r').unwrap()))
+    }
+
+    #[test]
+    fn part1_correct() {
+        let rucksacks = parse_input(TEST_INPUT).unwrap();
+        let value = part1(&rucksacks).unwrap();
+        assert_eq!(value, 157);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let rucksacks = parse_input(TEST_INPUT).unwrap();
+        let value = part2(&rucksacks).unwrap();
+        assert_eq!(value, 70);
+    }
+}
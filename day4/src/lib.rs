@@ -0,0 +1,113 @@
+use std::ops::RangeInclusive;
+
+use common::{
+    interval::IntervalSet,
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+
+type Range = RangeInclusive<i32>;
+
+#[derive(Debug, Clone)]
+pub struct AssignmentPair(Range, Range);
+
+fn parse_range(src: Source, s: &str) -> Result<Range, ParseError> {
+    let (start, end) = src.require(s.split_once('-'), s, "expected a range like 2-4")?;
+    Ok(Range::new(src.parse(start)?, src.parse(end)?))
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<AssignmentPair>> {
+    let src = Source::new(input);
+    let pairs = input
+        .lines()
+        .map(|l| {
+            let (rng1, rng2) = src.require(l.split_once(','), l, "expected two ranges")?;
+            Ok(AssignmentPair(
+                parse_range(src, rng1)?,
+                parse_range(src, rng2)?,
+            ))
+        })
+        .collect::<Result<_, ParseError>>()?;
+    Ok(pairs)
+}
+
+fn sections(pair: &AssignmentPair) -> (IntervalSet<i32>, IntervalSet<i32>) {
+    (pair.0.clone().into(), pair.1.clone().into())
+}
+
+fn either_is_subset(pair: &AssignmentPair) -> bool {
+    let (a, b) = sections(pair);
+    let both = a.intersection(&b);
+    both == a || both == b
+}
+
+fn is_any_overlap(pair: &AssignmentPair) -> bool {
+    let (a, b) = sections(pair);
+    !a.intersection(&b).is_empty()
+}
+
+fn part1(pairs: &[AssignmentPair]) -> usize {
+    pairs.iter().filter(|p| either_is_subset(p)).count()
+}
+
+fn part2(pairs: &[AssignmentPair]) -> usize {
+    pairs.iter().filter(|p| is_any_overlap(p)).count()
+}
+
+pub struct Day4;
+impl Solution for Day4 {
+    type Input = Vec<AssignmentPair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day4>(4, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::*;
+
+    const TEST_INPUT: &str = indoc! {"
+        2-4,6-8
+        2-3,4-5
+        5-7,7-9
+        2-8,3-7
+        6-6,4-6
+        2-6,4-8
+    "};
+
+    #[test]
+    fn part1_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&input);
+        assert_eq!(res, 2);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&input);
+        assert_eq!(res, 4);
+    }
+
+    #[test]
+    fn malformed_range_located() {
+        let err = parse_input("2-4,6-8\n2-3,4_5\n").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "4_5"));
+    }
+}
@@ -0,0 +1,196 @@
+use common::{
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+use regex::Regex;
+
+type Stack = Vec<char>;
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    count: usize,
+    source: usize,
+    dest: usize,
+}
+
+fn parse_input(input: &str) -> AnyResult<(Vec<Stack>, Vec<Instruction>)> {
+    let src = Source::new(input);
+    let input_lines: Vec<_> = input.lines().collect();
+    let mut it = input_lines.split(|l| l.is_empty());
+    let input_stacks = it.next().filter(|s| !s.is_empty());
+    let input_stacks = src.require(input_stacks, input, "missing stacks")?;
+    let input_instructions = it
+        .next()
+        .ok_or_else(|| src.end_error("missing instructions after the stacks"))?;
+
+    let stacks = parse_stacks(src, input_stacks)?;
+    let instructions = parse_instructions(src, input_instructions, stacks.len())?;
+
+    Ok((stacks, instructions))
+}
+
+/// Quick and dirty implementation - reverse the lines, then use the first
+/// line to determine how many stacks we have. Then iterate through the lines
+/// in reverse picking out chars at specific locations (since it is layed out
+/// spatially rather than delimited). Missing values are `None` are are not
+/// added to stacks.
+fn parse_stacks(src: Source, input: &[&str]) -> Result<Vec<Stack>, ParseError> {
+    let mut lines_iter = input.iter().rev();
+    let numbers_line = lines_iter.next().copied().unwrap_or_default();
+    let mut num_stacks = 0;
+    for number in numbers_line.split_whitespace() {
+        num_stacks = num_stacks.max(src.parse::<usize>(number)?);
+    }
+    if num_stacks == 0 {
+        return Err(src.error(numbers_line, "expected stack numbers"));
+    }
+
+    let mut stacks = vec![vec![]; num_stacks];
+    for l in lines_iter {
+        let lc: Vec<_> = l.chars().collect();
+        #[allow(clippy::needless_range_loop)]
+        for i in 0..num_stacks {
+            let location = 1 + i * 4;
+            let ch_maybe = lc.get(location).and_then(|c| match c {
+                ' ' => None,
+                c => Some(c),
+            });
+
+            if let Some(c) = ch_maybe {
+                stacks[i].push(*c);
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn parse_instructions(
+    src: Source,
+    input: &[&str],
+    num_stacks: usize,
+) -> Result<Vec<Instruction>, ParseError> {
+    // move 1 from 2 to 1
+    let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+
+    // stacks are numbered from 1 in the input
+    let stack_index = |field: &str| match src.parse::<usize>(field)? {
+        n @ 1.. if n <= num_stacks => Ok(n - 1),
+        _ => Err(src.error(field, format!("no such stack; expected 1 to {num_stacks}"))),
+    };
+
+    let mut instructions = vec![];
+    for l in input {
+        let cap = src.require(re.captures(l), l, "expected 'move N from A to B'")?;
+        instructions.push(Instruction {
+            count: src.parse(cap.get(1).unwrap().as_str())?,
+            source: stack_index(cap.get(2).unwrap().as_str())?,
+            dest: stack_index(cap.get(3).unwrap().as_str())?,
+        })
+    }
+    Ok(instructions)
+}
+
+// part 1 - old crane -- move crates one at a time
+fn part1_apply_instruction(stacks: &mut [Stack], instruction: &Instruction) {
+    for _ in 0..instruction.count {
+        let c = stacks[instruction.source].pop().unwrap();
+        stacks[instruction.dest].push(c);
+    }
+}
+
+fn part1(stacks: &[Stack], instructions: &[Instruction]) -> String {
+    let mut stacks = stacks.to_vec();
+    for inst in instructions.iter() {
+        part1_apply_instruction(&mut stacks, inst);
+    }
+    stacks.iter().map(|st| st.last().unwrap()).collect()
+}
+
+// part 2 - new crane -- move stack of crates all at once, so the order
+// is preserved on placing them on the top of another stack
+fn part2_apply_instruction(stacks: &mut [Stack], instruction: &Instruction) {
+    let mut collected = vec![];
+    for _ in 0..instruction.count {
+        let c = stacks[instruction.source].pop().unwrap();
+        collected.push(c);
+    }
+
+    for c in collected.iter().rev() {
+        stacks[instruction.dest].push(*c);
+    }
+}
+
+fn part2(stacks: &[Stack], instructions: &[Instruction]) -> String {
+    let mut stacks = stacks.to_vec();
+    for inst in instructions.iter() {
+        part2_apply_instruction(&mut stacks, inst);
+    }
+    stacks.iter().map(|st| st.last().unwrap()).collect()
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+    type Input = (Vec<Stack>, Vec<Instruction>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        let (stacks, instructions) = input;
+        Ok(part1(stacks, instructions))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        let (stacks, instructions) = input;
+        Ok(part2(stacks, instructions))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day5>(5, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+            [D]
+        [N] [C]
+        [Z] [M] [P]
+         1   2   3 
+        
+        move 1 from 2 to 1
+        move 3 from 1 to 3
+        move 2 from 2 to 1
+        move 1 from 1 to 2
+    "};
+
+    #[test]
+    fn part1_correct() {
+        let (stacks, instructions) = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&stacks, &instructions);
+        assert_eq!(res, "CMZ");
+    }
+
+    #[test]
+    fn part2_correct() {
+        let (stacks, instructions) = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&stacks, &instructions);
+        assert_eq!(res, "MCD");
+    }
+
+    #[test]
+    fn bad_instruction_located() {
+        let input = TEST_INPUT.replace("move 2 from 2 to 1", "move 2 from 4 to 1");
+        let err = parse_input(&input).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (8, 13, "4"));
+
+        let err = parse_input("    [D]\n 1   2 \n").unwrap_err();
+        assert!(err.downcast_ref::<ParseError>().is_some());
+    }
+}
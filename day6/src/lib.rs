@@ -0,0 +1,97 @@
+use common::{parse::Source, AnyResult, Day, Solution};
+
+// This is quite inefficient: O(N^2) on the size
+// of the slice. The problem is so small that a
+// more complex algorithm won't yield any benefit.
+fn all_different(characters: &[char]) -> bool {
+    let mut chr = characters;
+    while let Some((ch, remainder)) = chr.split_first() {
+        if remainder.iter().any(|r| r == ch) {
+            return false;
+        }
+        chr = remainder;
+    }
+    true
+}
+
+fn find_marker(line: &str, window_size: usize) -> usize {
+    let chars: Vec<_> = line.chars().collect();
+    let (idx, _) = chars
+        .windows(window_size)
+        .enumerate()
+        .find(|(_, w)| all_different(w))
+        .expect("pattern not found");
+    idx + window_size
+}
+
+// the datastream is a single line of lowercase letters
+fn parse_input(input: &str) -> AnyResult<String> {
+    let src = Source::new(input);
+    let stream = input.trim_end_matches('\n');
+    if let Some((i, ch)) = stream
+        .char_indices()
+        .find(|(_, ch)| !ch.is_ascii_lowercase())
+    {
+        let offending = &stream[i..i + ch.len_utf8()];
+        return Err(src
+            .error(offending, "unexpected character in datastream")
+            .into());
+    }
+    Ok(stream.to_owned())
+}
+
+fn part1(input: &str) -> usize {
+    find_marker(input, 4)
+}
+
+fn part2(input: &str) -> usize {
+    find_marker(input, 14)
+}
+
+pub struct Day6;
+impl Solution for Day6 {
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day6>(6, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn part1_examples_correct() {
+        assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
+        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+        assert_eq!(part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
+    }
+
+    #[test]
+    fn part2_examples_correct() {
+        assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
+        assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+        assert_eq!(part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
+    }
+
+    #[test]
+    fn parse_rejects_stray_characters() {
+        assert_eq!(parse_input("mjqjpq\n").unwrap(), "mjqjpq");
+        let err = parse_input("mjq jpq\n").unwrap_err();
+        let err = err.downcast_ref::<common::parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+}
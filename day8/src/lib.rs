@@ -0,0 +1,132 @@
+use std::iter::successors;
+
+use anyhow::anyhow;
+use common::{
+    geometry::{Dir4, Grid, Point},
+    AnyResult, Day, Solution,
+};
+
+type Pos = Point<isize>;
+
+/// The heights of the trees seen looking from `tree` towards `dir`, nearest
+/// first, up to the edge of the grid.
+fn line_of_sight(grid: &Grid<u8>, tree: Pos, dir: Dir4) -> impl Iterator<Item = u8> + '_ {
+    let step = Pos::from(dir);
+    successors(Some(tree + step), move |&p| Some(p + step)).map_while(|p| grid.get(p).copied())
+}
+
+fn visible_from_edge(grid: &Grid<u8>, tree: Pos) -> bool {
+    // trees on the edge have nothing in the way, so are always visible
+    let height = grid[tree];
+    Dir4::ALL
+        .into_iter()
+        .any(|dir| line_of_sight(grid, tree, dir).all(|h| h < height))
+}
+
+fn scenic_score_from_tree(grid: &Grid<u8>, tree: Pos) -> usize {
+    Dir4::ALL
+        .into_iter()
+        .map(|dir| visible_distance(grid, tree, dir))
+        .product()
+}
+
+fn visible_distance(grid: &Grid<u8>, tree: Pos, dir: Dir4) -> usize {
+    let own_height = grid[tree];
+    let mut distance = 0;
+    for h in line_of_sight(grid, tree, dir) {
+        distance += 1;
+        if h >= own_height {
+            break;
+        }
+    }
+    distance
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Grid<u8>> {
+    Grid::parse_chars(input, |ch| {
+        ch.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| anyhow!("expected a tree height digit"))
+    })
+}
+
+fn part1(grid: &Grid<u8>) -> usize {
+    grid.points()
+        .filter(|&tree| visible_from_edge(grid, tree))
+        .count()
+}
+
+fn part2(grid: &Grid<u8>) -> usize {
+    grid.points()
+        .map(|tree| scenic_score_from_tree(grid, tree))
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+    type Input = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day8>(8, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        30373
+        25512
+        65332
+        33549
+        35390
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        parse_input(TEST_INPUT).unwrap();
+    }
+
+    #[test]
+    fn part1_correct() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let res = part1(&grid);
+        assert_eq!(res, 21);
+    }
+
+    #[test]
+    fn scenic_score_correct() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(scenic_score_from_tree(&grid, Pos::new(2, 1)), 4);
+        assert_eq!(scenic_score_from_tree(&grid, Pos::new(2, 3)), 8);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let grid = parse_input(TEST_INPUT).unwrap();
+        let res = part2(&grid);
+        assert_eq!(res, 8);
+    }
+
+    #[test]
+    fn bad_height_located() {
+        let err = parse_input(&TEST_INPUT.replace("33549", "33x49")).unwrap_err();
+        let err = err.downcast_ref::<common::parse::ParseError>().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 3, "x"));
+    }
+}
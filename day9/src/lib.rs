@@ -0,0 +1,169 @@
+use common::{
+    geometry::{self, Dir4},
+    parse::{ParseError, Source},
+    AnyResult, Day, Solution,
+};
+use std::collections::HashSet;
+
+type Point = geometry::Point<i32>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction(Dir4, usize);
+impl Instruction {
+    fn parse(src: Source, line: &str) -> Result<Self, ParseError> {
+        let mut fields = line.split_whitespace();
+
+        let dir_field = src.require(fields.next(), line, "missing dir")?;
+        let repeat_field = src.require(fields.next(), line, "missing count")?;
+        if let Some(extra) = fields.next() {
+            return Err(src.error(extra, "unexpected trailing field"));
+        }
+
+        let dir = match dir_field {
+            "U" => Dir4::N,
+            "D" => Dir4::S,
+            "L" => Dir4::W,
+            "R" => Dir4::E,
+            _ => return Err(src.error(dir_field, "invalid dir; expected U, D, L or R")),
+        };
+        let repeat: usize = src.parse(repeat_field)?;
+
+        Ok(Instruction(dir, repeat))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rope(Vec<Point>);
+impl Rope {
+    fn new(length: usize) -> Self {
+        Rope(vec![Point::default(); length])
+    }
+
+    fn follow(leading: Point, current: Point) -> Point {
+        let distance = leading - current;
+        match distance {
+            Point {
+                x: -1..=1,
+                y: -1..=1,
+            } => current,
+            Point { x, y: 0 } => leading - Point::new(x.signum(), 0),
+            Point { x: 0, y } => leading - Point::new(0, y.signum()),
+            _ => current + distance.signum(),
+        }
+    }
+
+    fn move_dir(self, dir: Dir4) -> Rope {
+        let mut points = self.0;
+
+        // update head
+        points[0] += dir.into();
+
+        // rest of the points follow the changes
+        for i in 1..points.len() {
+            points[i] = Self::follow(points[i - 1], points[i]);
+        }
+
+        // updated rope
+        Rope(points)
+    }
+
+    fn tail(&self) -> Point {
+        *self.0.last().unwrap()
+    }
+}
+
+fn tail_position_count(instructions: &[Instruction], rope_length: usize) -> usize {
+    let mut history = HashSet::new();
+    let mut rope = Rope::new(rope_length);
+
+    history.insert(rope.tail());
+    for instruction in instructions {
+        for _ in 0..instruction.1 {
+            rope = rope.move_dir(instruction.0);
+            history.insert(rope.tail());
+        }
+    }
+    history.len()
+}
+
+fn part1(instructions: &[Instruction]) -> usize {
+    tail_position_count(instructions, 2)
+}
+
+fn part2(instructions: &[Instruction]) -> usize {
+    tail_position_count(instructions, 10)
+}
+
+fn parse_input(inputs: &str) -> anyhow::Result<Vec<Instruction>> {
+    let src = Source::new(inputs);
+    let instructions = inputs.lines().map(|l| Instruction::parse(src, l));
+    Ok(instructions.collect::<Result<_, _>>()?)
+}
+
+pub struct Day9;
+impl Solution for Day9 {
+    type Input = Vec<Instruction>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AnyResult<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> AnyResult<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> AnyResult<Self::Answer2> {
+        Ok(part2(input))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day9>(9, env!("CARGO_MANIFEST_DIR"));
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use indoc::indoc;
+
+    const TEST_INPUT_PART1: &str = indoc! {"
+        R 4
+        U 4
+        L 3
+        D 1
+        R 4
+        D 1
+        L 5
+        R 2
+    "};
+
+    const TEST_INPUT_PART2: &str = indoc! {"
+        R 5
+        U 8
+        L 8
+        D 3
+        R 17
+        D 10
+        L 25
+        U 20
+    "};
+
+    #[test]
+    fn parse_inputs_succeeds() {
+        parse_input(TEST_INPUT_PART1).unwrap();
+    }
+
+    #[test]
+    fn part1_correct() {
+        let instructions = parse_input(TEST_INPUT_PART1).unwrap();
+        let res = part1(&instructions);
+        assert_eq!(res, 13);
+    }
+
+    #[test]
+    fn part2_correct() {
+        let instructions = parse_input(TEST_INPUT_PART2).unwrap();
+        let res = part2(&instructions);
+        assert_eq!(res, 36);
+    }
+}